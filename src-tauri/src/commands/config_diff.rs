@@ -0,0 +1,158 @@
+use super::config_manager::read_file;
+use super::data::{get_app_local_data_dir, CONFIG_DIR};
+use super::ini_document::IniDocument;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::{path::BaseDirectory, AppHandle, Manager};
+
+/// Folder in the app local data directory that config backups are kept in.
+const BACKUP_DIR: &str = "config_backups";
+
+/// An INI file to compare.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum ConfigSource {
+    /// A file in the game's config directory, like `tribes.ini`
+    User { file: String },
+    /// A bundled preset, `file` defaults to `tribes.ini`
+    Preset {
+        variant: String,
+        file: Option<String>,
+    },
+    /// A backup by its file name, as listed by `get_backups`
+    Backup { name: String },
+}
+
+/// A key that differs between two INI files.
+#[derive(Debug, Serialize)]
+pub struct ConfigChange {
+    section: String,
+    key: String,
+    old: Option<String>,
+    new: Option<String>,
+}
+
+/// What changes when the source file is replaced by the target file.
+#[derive(Debug, Default, Serialize)]
+pub struct ConfigDiff {
+    /// Keys only in the target
+    added: Vec<ConfigChange>,
+    /// Keys only in the source
+    removed: Vec<ConfigChange>,
+    /// Keys in both with different values
+    changed: Vec<ConfigChange>,
+}
+
+/// Compare two INI files by section and key.
+///
+/// Keys that appear more than once in a section, like `Bindings`, are compared as sets of
+/// values, so each value only in one file is added or removed on its own.
+///
+/// # Arguments
+///
+/// * `handle` - The AppHandle object
+/// * `source` - The file as it is now, usually the user's file
+/// * `target` - The file that would replace it, like a preset or backup
+///
+/// # Returns
+///
+/// * `Result<ConfigDiff, String>` - The added, removed and changed keys or an error message
+#[tauri::command]
+pub fn diff_config(
+    handle: AppHandle,
+    source: ConfigSource,
+    target: ConfigSource,
+) -> Result<ConfigDiff, String> {
+    let source = IniDocument::parse(&read_file(&source_path(&handle, &source)?)?);
+    let target = IniDocument::parse(&read_file(&source_path(&handle, &target)?)?);
+    Ok(diff_documents(&source, &target))
+}
+
+fn diff_documents(source: &IniDocument, target: &IniDocument) -> ConfigDiff {
+    let mut sections: Vec<&str> = source.sections();
+    for section in target.sections() {
+        if !sections.iter().any(|s| s.eq_ignore_ascii_case(section)) {
+            sections.push(section);
+        }
+    }
+
+    let mut diff = ConfigDiff::default();
+    for section in sections {
+        let source_entries = source.entries(section);
+        let target_entries = target.entries(section);
+        let mut keys: Vec<&str> = Vec::new();
+        for (key, _) in source_entries.iter().chain(&target_entries) {
+            if !keys.iter().any(|k| k.eq_ignore_ascii_case(key)) {
+                keys.push(key);
+            }
+        }
+
+        for key in keys {
+            let change = |old: Option<&str>, new: Option<&str>| ConfigChange {
+                section: section.to_string(),
+                key: key.to_string(),
+                old: old.map(String::from),
+                new: new.map(String::from),
+            };
+            let mut old_values = source.get_all(section, key);
+            let mut new_values = target.get_all(section, key);
+
+            if old_values.len() <= 1 && new_values.len() <= 1 {
+                match (old_values.first(), new_values.first()) {
+                    (Some(old), Some(new)) if old != new => {
+                        diff.changed.push(change(Some(old), Some(new)))
+                    }
+                    (Some(old), None) => diff.removed.push(change(Some(old), None)),
+                    (None, Some(new)) => diff.added.push(change(None, Some(new))),
+                    _ => {}
+                }
+                continue;
+            }
+
+            // Pair off equal values, what is left over was added or removed
+            old_values.retain(|old| match new_values.iter().position(|new| new == old) {
+                Some(index) => {
+                    new_values.remove(index);
+                    false
+                }
+                None => true,
+            });
+            diff.removed
+                .extend(old_values.into_iter().map(|old| change(Some(old), None)));
+            diff.added
+                .extend(new_values.into_iter().map(|new| change(None, Some(new))));
+        }
+    }
+    diff
+}
+
+fn source_path(handle: &AppHandle, source: &ConfigSource) -> Result<PathBuf, String> {
+    match source {
+        ConfigSource::User { file } => Ok(CONFIG_DIR.join(file_name(file)?)),
+        ConfigSource::Preset { variant, file } => {
+            let file = file.as_deref().unwrap_or("tribes.ini");
+            handle
+                .path()
+                .resolve(
+                    format!(
+                        "../public/configs/{}/{}",
+                        file_name(variant)?,
+                        file_name(file)?
+                    ),
+                    BaseDirectory::Resource,
+                )
+                .map_err(|e| e.to_string())
+        }
+        ConfigSource::Backup { name } => Ok(get_app_local_data_dir(handle)
+            .join(BACKUP_DIR)
+            .join(file_name(name)?)),
+    }
+}
+
+/// Reject names that would reach outside their directory.
+fn file_name(name: &str) -> Result<&str, String> {
+    if name.is_empty() || name == ".." || name.contains(['/', '\\']) {
+        return Err(format!("Invalid config name: {}", name));
+    }
+    Ok(name)
+}
@@ -1,4 +1,7 @@
 use super::data::{CONFIG_DIR, CONFIG_FILES};
+use super::ini_document::IniDocument;
+use super::settings_schema::{find_setting, validate_setting};
+use serde::Deserialize;
 use std::fs::{self, copy, create_dir_all};
 use std::io::Read;
 use std::path::PathBuf;
@@ -64,8 +67,25 @@ pub fn fetch_config_files(handle: tauri::AppHandle) -> Result<ConfigFilesResult,
     })
 }
 
+/// A change to a key in an INI file.
+///
+/// Sent either as a `[key, value]` pair or as an object with a `section`. Known settings are
+/// only ever written to the sections the settings schema lists for them, so the section is
+/// optional for those and required for any other key.
+#[derive(Debug, Deserialize)]
+pub struct IniChange {
+    key: String,
+    value: String,
+    #[serde(default)]
+    section: Option<String>,
+}
+
 /// Update the tribes.ini file with the specified changes.
 ///
+/// Values of known settings are checked against the settings schema first, and nothing is
+/// written if any of them are invalid. Only the changed values are rewritten, the rest of
+/// the file is kept byte for byte.
+///
 /// # Arguments
 ///
 /// * `changes` - The changes to apply to the tribes.ini file.
@@ -74,14 +94,64 @@ pub fn fetch_config_files(handle: tauri::AppHandle) -> Result<ConfigFilesResult,
 ///
 /// An error message if the operation failed.
 #[tauri::command]
-pub fn update_ini_file(file: String, changes: Vec<(String, String)>) -> Result<(), String> {
-    let file_path = CONFIG_DIR.join(file);
+pub fn update_ini_file(file: String, changes: Vec<IniChange>) -> Result<(), String> {
+    let file_path = CONFIG_DIR.join(&file);
+
+    let mut document = IniDocument::parse(&read_file(&file_path)?);
+    for change in changes {
+        apply_ini_change(&mut document, &file, change)?;
+    }
 
-    let mut content = read_file(&file_path)?;
-    let mut lines: Vec<String> = content.split('\n').map(String::from).collect();
+    write_config_file(&file_path, &document.to_string())
+}
+
+/// Apply a single change, checking it against the settings schema.
+fn apply_ini_change(
+    document: &mut IniDocument,
+    file: &str,
+    change: IniChange,
+) -> Result<(), String> {
+    let Some(setting) = find_setting(file, &change.key) else {
+        // Unknown keys are written as given, but only where the caller says
+        let Some(section) = change.section else {
+            return Err(format!(
+                "{} is not a known setting, give a section to change it",
+                change.key
+            ));
+        };
+        document.set(&section, &change.key, &change.value);
+        return Ok(());
+    };
 
+    let value = validate_setting(setting, &change.value)?;
+    match change.section {
+        Some(section) => {
+            if !setting
+                .sections
+                .iter()
+                .any(|s| s.eq_ignore_ascii_case(&section))
+            {
+                return Err(format!(
+                    "{} is not read from section {}",
+                    change.key, section
+                ));
+            }
+            document.set(&section, setting.key, &value);
+        }
+        // Some settings, like bForceStaticTerrain, are read from several sections
+        None => {
+            for section in setting.sections {
+                document.set(section, setting.key, &value);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Write a config file, keeping it read-only if it was.
+pub(crate) fn write_config_file(file_path: &PathBuf, content: &str) -> Result<(), String> {
     // Check if the file is read-only
-    let metadata = fs::metadata(&file_path).map_err(|e| {
+    let metadata = fs::metadata(file_path).map_err(|e| {
         format!(
             "Failed to get metadata for file {}: {}",
             file_path.display(),
@@ -95,7 +165,7 @@ pub fn update_ini_file(file: String, changes: Vec<(String, String)>) -> Result<(
         // Make the file writable
         let mut permissions = metadata.permissions();
         permissions.set_readonly(false);
-        fs::set_permissions(&file_path, permissions).map_err(|e| {
+        fs::set_permissions(file_path, permissions).map_err(|e| {
             format!(
                 "Failed to set permissions for file {}: {}",
                 file_path.display(),
@@ -104,34 +174,16 @@ pub fn update_ini_file(file: String, changes: Vec<(String, String)>) -> Result<(
         })?;
     }
 
-    for (key, new_value) in changes {
-        // Capitalize boolean values
-        let formatted_value = if new_value == "true" || new_value == "false" {
-            let mut chars = new_value.chars();
-            chars.next().unwrap().to_uppercase().collect::<String>() + chars.as_str()
-        } else {
-            new_value
-        };
-
-        // Update all occurrences of the key (Used for instances such as force static terrain which appear multiple times)
-        for line in lines.iter_mut() {
-            if line.starts_with(&format!("{}=", key)) {
-                *line = format!("{}={}", key, formatted_value);
-            }
-        }
-    }
-
-    content = lines.join("\n");
-    fs::write(&file_path, content)
+    fs::write(file_path, content)
         .map_err(|e| format!("Failed to write to file {}: {}", file_path.display(), e))?;
 
     // Restore the read-only permission if it was initially read-only
     if was_read_only {
-        let mut permissions = fs::metadata(&file_path)
+        let mut permissions = fs::metadata(file_path)
             .map_err(|e| e.to_string())?
             .permissions();
         permissions.set_readonly(true);
-        fs::set_permissions(&file_path, permissions).map_err(|e| {
+        fs::set_permissions(file_path, permissions).map_err(|e| {
             format!(
                 "Failed to restore permissions for file {}: {}",
                 file_path.display(),
@@ -152,7 +204,7 @@ fn read_config_file(path: &PathBuf) -> Result<ConfigFile, String> {
 }
 
 /// Read the content of a file.
-fn read_file(path: &PathBuf) -> Result<String, String> {
+pub(crate) fn read_file(path: &PathBuf) -> Result<String, String> {
     let mut file = fs::File::open(path)
         .map_err(|e| format!("Failed to open file {}: {}", path.display(), e))?;
     let mut content = String::new();
@@ -173,3 +225,78 @@ fn get_permissions(path: &PathBuf) -> Result<String, String> {
     }
     .to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRIBES_INI: &str = include_str!("../../../public/configs/defaultini/tribes.ini");
+
+    fn change(key: &str, value: &str, section: Option<&str>) -> IniChange {
+        IniChange {
+            key: key.to_string(),
+            value: value.to_string(),
+            section: section.map(String::from),
+        }
+    }
+
+    #[test]
+    fn changes_parse_from_pairs_and_objects() {
+        let changes: Vec<IniChange> =
+            serde_json::from_str(r#"[["ResX","1280"],{"key":"k","value":"v","section":"S"}]"#)
+                .unwrap();
+        assert_eq!(changes[0].key, "ResX");
+        assert!(changes[0].section.is_none());
+        assert_eq!(changes[1].section.as_deref(), Some("S"));
+    }
+
+    #[test]
+    fn known_settings_are_only_written_to_their_sections() {
+        let content = format!("{}\n[Other]\nResX=640\n", TRIBES_INI);
+        let mut document = IniDocument::parse(&content);
+        apply_ini_change(&mut document, "tribes.ini", change("resx", "1280", None)).unwrap();
+
+        assert_eq!(document.get("SystemSettings", "ResX"), Some("1280"));
+        assert_eq!(document.get("Other", "ResX"), Some("640"));
+        assert!(apply_ini_change(
+            &mut document,
+            "tribes.ini",
+            change("ResX", "1280", Some("Other"))
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn settings_read_from_several_sections_are_written_to_each() {
+        let mut document = IniDocument::parse(TRIBES_INI);
+        let terrain = change("bForceStaticTerrain", "false", None);
+        apply_ini_change(&mut document, "tribes.ini", terrain).unwrap();
+
+        for section in ["TribesGame.TrGameEngine", "Engine.Engine"] {
+            assert_eq!(document.get(section, "bForceStaticTerrain"), Some("False"));
+        }
+    }
+
+    #[test]
+    fn unknown_keys_need_a_section() {
+        let mut document = IniDocument::parse(TRIBES_INI);
+        let before = document.to_string();
+        assert!(apply_ini_change(&mut document, "tribes.ini", change("Gamma", "2", None)).is_err());
+        assert_eq!(document.to_string(), before);
+
+        let gamma = change("Gamma", "2", Some("Engine.Client"));
+        apply_ini_change(&mut document, "tribes.ini", gamma).unwrap();
+        assert_eq!(document.get("Engine.Client", "Gamma"), Some("2"));
+    }
+
+    #[test]
+    fn invalid_values_are_rejected() {
+        let mut document = IniDocument::parse(TRIBES_INI);
+        assert!(
+            apply_ini_change(&mut document, "tribes.ini", change("ResX", "wide", None)).is_err()
+        );
+        assert!(
+            apply_ini_change(&mut document, "tribes.ini", change("Bloom", "yes", None)).is_err()
+        );
+    }
+}
@@ -1,6 +1,9 @@
 use once_cell::sync::Lazy;
 use serde_json::Value;
-use std::{fs, path::PathBuf};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 use tauri::{path::BaseDirectory, AppHandle, Manager};
 
 /// Store the name and default path of a config file.
@@ -12,8 +15,6 @@ pub struct ConfigFileInfo {
 pub const PKG_ENDPOINT: &str = "https://client.update.tamods.org/"; // The endpoint for downloading packages
 pub const PKG_CFG_FILE: &str = "packageconfig.yaml"; // File that contains package list and dependencies
 
-pub const CREATE_NO_WINDOW: u32 = 0x08000000; // Flag to create a window with no UI
-
 pub static DOCS_DIR: Lazy<PathBuf> = Lazy::new(|| {
     // The user's documents directory
     dirs::document_dir().expect("Failed to get documents directory")
@@ -167,3 +168,12 @@ pub fn get_original_dlls_dir(handle: &AppHandle) -> Result<PathBuf, String> {
         Err(e) => Err(format!("Failed to get original DLLs directory: {}", e)),
     }
 }
+
+/// Rename a file, falling back to copy and delete when the two paths are on different drives.
+pub(crate) fn move_file(from: &Path, to: &Path) -> std::io::Result<()> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    fs::copy(from, to)?;
+    fs::remove_file(from)
+}
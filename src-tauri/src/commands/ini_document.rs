@@ -0,0 +1,441 @@
+use std::fmt;
+
+/// What a line in an INI file holds.
+#[derive(Debug, Clone, PartialEq)]
+enum LineKind {
+    /// `[Name]`
+    Section(String),
+    /// `Key=Value`, with the value starting after the first `=`
+    Entry { key: String, value_start: usize },
+    /// Blank lines, comments and anything else, kept as they are
+    Other,
+}
+
+#[derive(Debug, Clone)]
+struct IniLine {
+    text: String,
+    /// `\n`, `\r\n`, or empty for a last line with no line ending
+    ending: String,
+    kind: LineKind,
+}
+
+impl IniLine {
+    fn new(text: String, ending: String) -> Self {
+        let kind = classify(&text);
+        IniLine { text, ending, kind }
+    }
+
+    fn value(&self) -> Option<&str> {
+        match &self.kind {
+            LineKind::Entry { value_start, .. } => Some(&self.text[*value_start..]),
+            _ => None,
+        }
+    }
+
+    fn is_key(&self, key: &str) -> bool {
+        matches!(&self.kind, LineKind::Entry { key: k, .. } if k.eq_ignore_ascii_case(key))
+    }
+}
+
+/// An INI file that keeps every line as it was read.
+///
+/// Writing an unchanged document gives back the exact text it was parsed from, including
+/// comments, blank lines, line endings and keys repeated in a section (like `Bindings=`).
+/// Sections and keys are matched ignoring ASCII case, as the game does. A section that
+/// appears more than once is treated as one section.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct IniDocument {
+    lines: Vec<IniLine>,
+}
+
+impl IniDocument {
+    pub(crate) fn parse(content: &str) -> Self {
+        let mut lines = Vec::new();
+        let mut rest = content;
+        while !rest.is_empty() {
+            let (line, ending, next) = match rest.find('\n') {
+                Some(i) if rest[..i].ends_with('\r') => (&rest[..i - 1], "\r\n", &rest[i + 1..]),
+                Some(i) => (&rest[..i], "\n", &rest[i + 1..]),
+                None => (rest, "", ""),
+            };
+            lines.push(IniLine::new(line.to_string(), ending.to_string()));
+            rest = next;
+        }
+        IniDocument { lines }
+    }
+
+    /// The names of every section, in file order, each named once.
+    pub(crate) fn sections(&self) -> Vec<&str> {
+        let mut sections: Vec<&str> = Vec::new();
+        for line in &self.lines {
+            if let LineKind::Section(name) = &line.kind {
+                if !sections.iter().any(|s| s.eq_ignore_ascii_case(name)) {
+                    sections.push(name);
+                }
+            }
+        }
+        sections
+    }
+
+    /// The keys and values in a section, in file order.
+    pub(crate) fn entries(&self, section: &str) -> Vec<(&str, &str)> {
+        self.section_line_indices(section)
+            .into_iter()
+            .filter_map(|i| match &self.lines[i].kind {
+                LineKind::Entry { key, value_start } => {
+                    Some((key.as_str(), &self.lines[i].text[*value_start..]))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The first value of a key in a section.
+    pub(crate) fn get(&self, section: &str, key: &str) -> Option<&str> {
+        self.get_all(section, key).into_iter().next()
+    }
+
+    /// Every value of a key in a section, for keys that repeat like `Bindings`.
+    pub(crate) fn get_all(&self, section: &str, key: &str) -> Vec<&str> {
+        self.section_line_indices(section)
+            .into_iter()
+            .filter(|&i| self.lines[i].is_key(key))
+            .filter_map(|i| self.lines[i].value())
+            .collect()
+    }
+
+    /// Set every occurrence of a key in a section, adding it if the section does not have it.
+    pub(crate) fn set(&mut self, section: &str, key: &str, value: &str) {
+        let indices: Vec<usize> = self
+            .section_line_indices(section)
+            .into_iter()
+            .filter(|&i| self.lines[i].is_key(key))
+            .collect();
+        if indices.is_empty() {
+            self.insert(section, key, value);
+            return;
+        }
+
+        for i in indices {
+            let line = &mut self.lines[i];
+            if let LineKind::Entry { value_start, .. } = line.kind {
+                line.text.truncate(value_start);
+                line.text.push_str(value);
+            }
+        }
+    }
+
+    /// Add a key after the last entry of a section, creating the section at the end of the
+    /// file if it does not exist. Existing occurrences of the key are kept.
+    pub(crate) fn insert(&mut self, section: &str, key: &str, value: &str) {
+        let line = format!("{}={}", key, value);
+        let index = match self.section_insert_index(section) {
+            Some(index) => index,
+            None => {
+                // Separate the new section from the one before it with a blank line
+                if self.lines.last().is_some_and(|l| !l.text.trim().is_empty()) {
+                    self.insert_line(self.lines.len(), String::new());
+                }
+                self.insert_line(self.lines.len(), format!("[{}]", section));
+                self.lines.len()
+            }
+        };
+        self.insert_line(index, line);
+    }
+
+    /// Change the value of a key in a section only where it has the given value, keeping its
+    /// place in the file.
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The number of lines changed
+    pub(crate) fn replace(&mut self, section: &str, key: &str, old: &str, new: &str) -> usize {
+        let indices: Vec<usize> = self
+            .section_line_indices(section)
+            .into_iter()
+            .filter(|&i| self.lines[i].is_key(key) && self.lines[i].value() == Some(old))
+            .collect();
+
+        for &i in &indices {
+            let line = &mut self.lines[i];
+            if let LineKind::Entry { value_start, .. } = line.kind {
+                line.text.truncate(value_start);
+                line.text.push_str(new);
+            }
+        }
+        indices.len()
+    }
+
+    /// Remove a key from a section, only where it has the given value if one is given.
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The number of lines removed
+    pub(crate) fn remove(&mut self, section: &str, key: &str, value: Option<&str>) -> usize {
+        let mut indices: Vec<usize> = self
+            .section_line_indices(section)
+            .into_iter()
+            .filter(|&i| self.lines[i].is_key(key))
+            .filter(|&i| value.is_none_or(|value| self.lines[i].value() == Some(value)))
+            .collect();
+
+        // Keep a file that had no line ending at the end that way
+        let last = self.lines.len().checked_sub(1);
+        let keep_unterminated = indices.last() == last.as_ref()
+            && last.is_some_and(|last| self.lines[last].ending.is_empty());
+
+        let removed = indices.len();
+        while let Some(i) = indices.pop() {
+            self.lines.remove(i);
+        }
+        if keep_unterminated {
+            if let Some(last) = self.lines.last_mut() {
+                last.ending.clear();
+            }
+        }
+        removed
+    }
+
+    /// Indices of every line inside a section, headers excluded.
+    fn section_line_indices(&self, section: &str) -> Vec<usize> {
+        let mut indices = Vec::new();
+        let mut inside = false;
+        for (i, line) in self.lines.iter().enumerate() {
+            match &line.kind {
+                LineKind::Section(name) => inside = name.eq_ignore_ascii_case(section),
+                _ if inside => indices.push(i),
+                _ => {}
+            }
+        }
+        indices
+    }
+
+    /// Where a new entry in a section goes: after the last non-blank line of its last block.
+    fn section_insert_index(&self, section: &str) -> Option<usize> {
+        let mut index = None;
+        let mut inside = false;
+        for (i, line) in self.lines.iter().enumerate() {
+            match &line.kind {
+                LineKind::Section(name) => {
+                    inside = name.eq_ignore_ascii_case(section);
+                    if inside {
+                        index = Some(i + 1);
+                    }
+                }
+                _ if inside && !line.text.trim().is_empty() => index = Some(i + 1),
+                _ => {}
+            }
+        }
+        index
+    }
+
+    /// Insert a line, giving it and the line before it the file's line ending as needed.
+    fn insert_line(&mut self, index: usize, text: String) {
+        let newline = self
+            .lines
+            .iter()
+            .map(|l| l.ending.as_str())
+            .find(|ending| !ending.is_empty())
+            .unwrap_or("\n")
+            .to_string();
+
+        let ending = if index == self.lines.len() {
+            // The new line becomes the last, so it takes over the old last line's ending
+            match self.lines.last_mut() {
+                Some(last) if last.ending.is_empty() => {
+                    last.ending = newline;
+                    String::new()
+                }
+                _ => newline,
+            }
+        } else {
+            newline
+        };
+        self.lines.insert(index, IniLine::new(text, ending));
+    }
+}
+
+impl fmt::Display for IniDocument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.lines {
+            f.write_str(&line.text)?;
+            f.write_str(&line.ending)?;
+        }
+        Ok(())
+    }
+}
+
+fn classify(text: &str) -> LineKind {
+    let trimmed = text.trim_start_matches('\u{feff}').trim();
+    if trimmed.is_empty() || trimmed.starts_with(';') || trimmed.starts_with('#') {
+        return LineKind::Other;
+    }
+    if let Some(name) = trimmed.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+        return LineKind::Section(name.trim().to_string());
+    }
+    match text.find('=') {
+        Some(equals) => LineKind::Entry {
+            key: text[..equals]
+                .trim_start_matches('\u{feff}')
+                .trim()
+                .to_string(),
+            value_start: equals + 1,
+        },
+        None => LineKind::Other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The config files the launcher ships, by name.
+    const PRESETS: [(&str, &str); 9] = [
+        (
+            "defaultini/tribes.ini",
+            include_str!("../../../public/configs/defaultini/tribes.ini"),
+        ),
+        (
+            "defaultinput/TribesInput.ini",
+            include_str!("../../../public/configs/defaultinput/TribesInput.ini"),
+        ),
+        (
+            "defaulttribesuser/TribesUser.ini",
+            include_str!("../../../public/configs/defaulttribesuser/TribesUser.ini"),
+        ),
+        (
+            "defaulttribeshelptext/TribesHelpText.ini",
+            include_str!("../../../public/configs/defaulttribeshelptext/TribesHelpText.ini"),
+        ),
+        (
+            "dodgehigh/tribes.ini",
+            include_str!("../../../public/configs/dodgehigh/tribes.ini"),
+        ),
+        (
+            "evil/tribes.ini",
+            include_str!("../../../public/configs/evil/tribes.ini"),
+        ),
+        (
+            "evillow/tribes.ini",
+            include_str!("../../../public/configs/evillow/tribes.ini"),
+        ),
+        (
+            "veryhigh/tribes.ini",
+            include_str!("../../../public/configs/veryhigh/tribes.ini"),
+        ),
+        (
+            "verylow/tribes.ini",
+            include_str!("../../../public/configs/verylow/tribes.ini"),
+        ),
+    ];
+
+    const TRIBES_INI: &str = PRESETS[0].1;
+    const TRIBES_INPUT_INI: &str = PRESETS[1].1;
+
+    fn round_trip(content: &str) -> String {
+        IniDocument::parse(content).to_string()
+    }
+
+    /// The lines that differ between two texts of the same line count.
+    fn changed_lines<'a>(before: &'a str, after: &'a str) -> Vec<(&'a str, &'a str)> {
+        assert_eq!(before.lines().count(), after.lines().count());
+        before
+            .lines()
+            .zip(after.lines())
+            .filter(|(a, b)| a != b)
+            .collect()
+    }
+
+    #[test]
+    fn presets_round_trip_byte_for_byte() {
+        for (name, content) in PRESETS {
+            assert_eq!(round_trip(content), content, "{}", name);
+        }
+    }
+
+    #[test]
+    fn line_endings_bom_and_missing_final_newline_round_trip() {
+        for (name, content) in PRESETS {
+            let crlf = content.replace('\n', "\r\n");
+            assert_eq!(round_trip(&crlf), crlf, "{}", name);
+
+            let bom = format!("\u{feff}{}", content.trim_end_matches('\n'));
+            assert_eq!(round_trip(&bom), bom, "{}", name);
+        }
+
+        let mixed = "; comment\r\n[A]\nx=1\r\n\r\n  y = 2 \n[B]";
+        assert_eq!(round_trip(mixed), mixed);
+    }
+
+    #[test]
+    fn set_only_changes_the_key_in_its_section() {
+        let mut document = IniDocument::parse(TRIBES_INI);
+        assert_eq!(
+            document.get_all("Engine.Engine", "bForceStaticTerrain"),
+            vec!["True"]
+        );
+        document.set("engine.engine", "bforcestaticterrain", "False");
+
+        assert_eq!(
+            changed_lines(TRIBES_INI, &document.to_string()),
+            vec![("bForceStaticTerrain=True", "bForceStaticTerrain=False")]
+        );
+        assert_eq!(
+            document.get("TribesGame.TrGameEngine", "bForceStaticTerrain"),
+            Some("True")
+        );
+    }
+
+    #[test]
+    fn repeated_keys_are_kept_in_order() {
+        let mut document = IniDocument::parse(TRIBES_INPUT_INI);
+        let bindings = document.get_all("Engine.PlayerInput", "Bindings");
+        assert!(bindings.len() > 1);
+        let first = bindings[0].to_string();
+        let second = bindings[1].to_string();
+
+        assert_eq!(
+            document.replace("Engine.PlayerInput", "Bindings", &first, "(Name=\"F1\")"),
+            1
+        );
+        assert_eq!(
+            changed_lines(TRIBES_INPUT_INI, &document.to_string()),
+            vec![(
+                format!("Bindings={}", first).as_str(),
+                "Bindings=(Name=\"F1\")"
+            )]
+        );
+        assert_eq!(
+            document.get_all("Engine.PlayerInput", "Bindings")[1],
+            second
+        );
+
+        assert_eq!(
+            document.remove("Engine.PlayerInput", "Bindings", Some("(Name=\"F1\")")),
+            1
+        );
+        let removed = document.to_string();
+        assert_eq!(
+            removed,
+            TRIBES_INPUT_INI.replacen(&format!("Bindings={}\n", first), "", 1)
+        );
+    }
+
+    #[test]
+    fn insert_adds_to_the_end_of_a_section_or_a_new_section() {
+        let mut document = IniDocument::parse("; comment\n[A]\nx=1\nb=2\n\n[B]\nx=1\n");
+        document.insert("a", "b", "3");
+        document.set("C", "k", "v");
+        assert_eq!(
+            document.to_string(),
+            "; comment\n[A]\nx=1\nb=2\nb=3\n\n[B]\nx=1\n\n[C]\nk=v\n"
+        );
+        assert_eq!(document.get_all("A", "B"), vec!["2", "3"]);
+
+        let mut document = IniDocument::parse("[A]\r\nx=1");
+        document.insert("A", "y", "2");
+        assert_eq!(document.to_string(), "[A]\r\nx=1\r\ny=2");
+        document.remove("A", "y", None);
+        assert_eq!(document.to_string(), "[A]\r\nx=1");
+    }
+}
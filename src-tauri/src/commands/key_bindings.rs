@@ -0,0 +1,391 @@
+use super::config_manager::{read_file, write_config_file};
+use super::data::CONFIG_DIR;
+use super::ini_document::IniDocument;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The config file that holds the key bindings.
+pub(crate) const INPUT_FILE: &str = "TribesInput.ini";
+
+/// The INI key every binding is stored under.
+pub(crate) const BINDINGS_KEY: &str = "Bindings";
+
+/// One `Bindings=(...)` line in TribesInput.ini.
+///
+/// `name` is the key (`W`, `LeftMouseButton`) or, for aliases, the name other bindings use as
+/// a command. `command` may chain several commands with `|`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyBinding {
+    pub(crate) name: String,
+    pub(crate) command: String,
+    pub(crate) control: bool,
+    pub(crate) shift: bool,
+    pub(crate) alt: bool,
+    pub(crate) ignore_ctrl: bool,
+    pub(crate) ignore_shift: bool,
+    pub(crate) ignore_alt: bool,
+}
+
+impl KeyBinding {
+    /// Parse the value of a `Bindings=` line.
+    ///
+    /// Fields can come in any order and names may be quoted or not. Missing modifiers are
+    /// false, and anything after the closing bracket is ignored.
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        let fields = value.trim().strip_prefix('(')?;
+        let mut binding = KeyBinding::default();
+        let mut has_name = false;
+
+        for field in split_fields(fields) {
+            let Some((field, value)) = field.split_once('=') else {
+                continue;
+            };
+            let value = value.trim();
+            let text = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .unwrap_or(value)
+                .to_string();
+            let flag = value.eq_ignore_ascii_case("true");
+            match field.trim().to_ascii_lowercase().as_str() {
+                "name" => {
+                    binding.name = text;
+                    has_name = true;
+                }
+                "command" => binding.command = text,
+                "control" => binding.control = flag,
+                "shift" => binding.shift = flag,
+                "alt" => binding.alt = flag,
+                "bignorectrl" => binding.ignore_ctrl = flag,
+                "bignoreshift" => binding.ignore_shift = flag,
+                "bignorealt" => binding.ignore_alt = flag,
+                _ => {}
+            }
+        }
+
+        has_name.then_some(binding)
+    }
+
+    /// Whether two bindings fire on the same key and modifiers.
+    fn same_keys(&self, other: &KeyBinding) -> bool {
+        self.name.eq_ignore_ascii_case(&other.name)
+            && self.control == other.control
+            && self.shift == other.shift
+            && self.alt == other.alt
+    }
+
+    /// The key and modifiers as shown to players, like `Ctrl+Shift+W`.
+    pub(crate) fn keys_label(&self) -> String {
+        let mut label = String::new();
+        for (held, modifier) in [
+            (self.control, "Ctrl+"),
+            (self.shift, "Shift+"),
+            (self.alt, "Alt+"),
+        ] {
+            if held {
+                label.push_str(modifier);
+            }
+        }
+        label + &self.name
+    }
+
+    /// Check that the binding can be written to a `Bindings=` line and read back.
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("A binding needs a key name".into());
+        }
+        if self.name.contains(['"', ',', '(', ')', '\n', '\r']) {
+            return Err(format!("Invalid key name: {}", self.name));
+        }
+        if self.command.contains('"') {
+            return Err("Binding commands cannot contain quotes".into());
+        }
+        if self.command.contains(['\n', '\r']) {
+            return Err("Binding commands cannot contain line breaks".into());
+        }
+        Ok(())
+    }
+}
+
+/// Written the way the game writes bindings, with every field.
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let flag = |value: bool| if value { "True" } else { "False" };
+        write!(
+            f,
+            "(Name=\"{}\",Command=\"{}\",Control={},Shift={},Alt={},bIgnoreCtrl={},bIgnoreShift={},bIgnoreAlt={})",
+            self.name,
+            self.command,
+            flag(self.control),
+            flag(self.shift),
+            flag(self.alt),
+            flag(self.ignore_ctrl),
+            flag(self.ignore_shift),
+            flag(self.ignore_alt)
+        )
+    }
+}
+
+/// A key and modifiers bound more than once in a section.
+#[derive(Debug, Serialize)]
+pub struct BindingConflict {
+    keys: String,
+    commands: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SectionBindings {
+    section: String,
+    bindings: Vec<KeyBinding>,
+    conflicts: Vec<BindingConflict>,
+}
+
+/// List the key bindings in TribesInput.ini, by section in file order.
+///
+/// # Returns
+///
+/// * `Result<Vec<SectionBindings>, String>` - The bindings and conflicts of every section that
+///   has bindings or an error message
+#[tauri::command]
+pub fn list_key_bindings() -> Result<Vec<SectionBindings>, String> {
+    let document = read_input_file()?;
+    Ok(document
+        .sections()
+        .into_iter()
+        .map(|section| {
+            let bindings = section_bindings(&document, section);
+            SectionBindings {
+                section: section.to_string(),
+                conflicts: find_conflicts(&bindings),
+                bindings,
+            }
+        })
+        .filter(|section| !section.bindings.is_empty())
+        .collect())
+}
+
+/// Add a key binding to the end of a section.
+///
+/// # Arguments
+///
+/// * `section` - The section to add the binding to, like `Engine.PlayerInput`
+/// * `binding` - The binding to add
+/// * `replace_existing` - Remove bindings on the same key and modifiers instead of failing
+///
+/// # Returns
+///
+/// * `Result<(), String>` - Ok if the binding was added or an error message, including when
+///   the key is already bound
+#[tauri::command]
+pub fn add_key_binding(
+    section: String,
+    binding: KeyBinding,
+    replace_existing: Option<bool>,
+) -> Result<(), String> {
+    binding.validate()?;
+
+    let mut document = read_input_file()?;
+    resolve_conflicts(
+        &mut document,
+        &section,
+        &binding,
+        None,
+        replace_existing.unwrap_or(false),
+    )?;
+    document.insert(&section, BINDINGS_KEY, &binding.to_string());
+    write_input_file(&document)
+}
+
+/// Change an existing key binding in place, usually to move it to another key.
+///
+/// # Arguments
+///
+/// * `section` - The section the binding is in
+/// * `binding` - The binding as it is now
+/// * `new_binding` - The binding to replace it with
+/// * `replace_existing` - Remove other bindings on the new key and modifiers instead of failing
+///
+/// # Returns
+///
+/// * `Result<(), String>` - Ok if the binding was changed or an error message, including when
+///   the new key is already bound
+#[tauri::command]
+pub fn rebind_key_binding(
+    section: String,
+    binding: KeyBinding,
+    new_binding: KeyBinding,
+    replace_existing: Option<bool>,
+) -> Result<(), String> {
+    new_binding.validate()?;
+
+    let mut document = read_input_file()?;
+    let Some(old) = find_binding_lines(&document, &section, &binding)
+        .into_iter()
+        .next()
+    else {
+        return Err(format!(
+            "{} is not bound to {} in {}",
+            binding.keys_label(),
+            binding.command,
+            section
+        ));
+    };
+
+    resolve_conflicts(
+        &mut document,
+        &section,
+        &new_binding,
+        Some(&binding),
+        replace_existing.unwrap_or(false),
+    )?;
+    document.replace(&section, BINDINGS_KEY, &old, &new_binding.to_string());
+    write_input_file(&document)
+}
+
+/// Remove a key binding.
+///
+/// # Arguments
+///
+/// * `section` - The section the binding is in
+/// * `binding` - The binding to remove
+///
+/// # Returns
+///
+/// * `Result<usize, String>` - The number of lines removed or an error message
+#[tauri::command]
+pub fn remove_key_binding(section: String, binding: KeyBinding) -> Result<usize, String> {
+    let mut document = read_input_file()?;
+    let lines = find_binding_lines(&document, &section, &binding);
+    if lines.is_empty() {
+        return Err(format!(
+            "{} is not bound to {} in {}",
+            binding.keys_label(),
+            binding.command,
+            section
+        ));
+    }
+
+    let removed = lines
+        .iter()
+        .map(|line| document.remove(&section, BINDINGS_KEY, Some(line)))
+        .sum();
+    write_input_file(&document)?;
+    Ok(removed)
+}
+
+/// Every binding in a section, skipping lines that cannot be parsed.
+pub(crate) fn section_bindings(document: &IniDocument, section: &str) -> Vec<KeyBinding> {
+    document
+        .get_all(section, BINDINGS_KEY)
+        .into_iter()
+        .filter_map(KeyBinding::parse)
+        .collect()
+}
+
+/// Group bindings that share a key and modifiers.
+fn find_conflicts(bindings: &[KeyBinding]) -> Vec<BindingConflict> {
+    let mut conflicts: Vec<(&KeyBinding, Vec<String>)> = Vec::new();
+    for binding in bindings {
+        match conflicts
+            .iter_mut()
+            .find(|(first, _)| first.same_keys(binding))
+        {
+            Some((_, commands)) => commands.push(binding.command.clone()),
+            None => conflicts.push((binding, vec![binding.command.clone()])),
+        }
+    }
+
+    conflicts
+        .into_iter()
+        .filter(|(_, commands)| commands.len() > 1)
+        .map(|(first, commands)| BindingConflict {
+            keys: first.keys_label(),
+            commands,
+        })
+        .collect()
+}
+
+/// The raw values of the lines in a section that hold a binding.
+pub(crate) fn find_binding_lines(
+    document: &IniDocument,
+    section: &str,
+    binding: &KeyBinding,
+) -> Vec<String> {
+    let mut lines: Vec<String> = document
+        .get_all(section, BINDINGS_KEY)
+        .into_iter()
+        .filter(|line| KeyBinding::parse(line).as_ref() == Some(binding))
+        .map(String::from)
+        .collect();
+    lines.dedup();
+    lines
+}
+
+/// Fail if a binding's key is already bound in a section, or remove the other bindings when
+/// `replace_existing` is set. `except` is a binding that is about to be replaced.
+pub(crate) fn resolve_conflicts(
+    document: &mut IniDocument,
+    section: &str,
+    binding: &KeyBinding,
+    except: Option<&KeyBinding>,
+    replace_existing: bool,
+) -> Result<(), String> {
+    let conflicts: Vec<KeyBinding> = section_bindings(document, section)
+        .into_iter()
+        .filter(|other| other.same_keys(binding) && Some(other) != except)
+        .collect();
+    if conflicts.is_empty() {
+        return Ok(());
+    }
+
+    if !replace_existing {
+        let commands: Vec<&str> = conflicts.iter().map(|c| c.command.as_str()).collect();
+        return Err(format!(
+            "{} is already bound to {} in {}",
+            binding.keys_label(),
+            commands.join(", "),
+            section
+        ));
+    }
+
+    for conflict in conflicts {
+        for line in find_binding_lines(document, section, &conflict) {
+            document.remove(section, BINDINGS_KEY, Some(&line));
+        }
+    }
+    Ok(())
+}
+
+/// Split the fields of a binding on commas outside quotes, stopping at the closing bracket.
+fn split_fields(fields: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut quoted = false;
+    let mut start = 0;
+    for (i, c) in fields.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            ',' if !quoted => {
+                parts.push(&fields[start..i]);
+                start = i + 1;
+            }
+            ')' if !quoted => {
+                parts.push(&fields[start..i]);
+                return parts;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&fields[start..]);
+    parts
+}
+
+pub(crate) fn read_input_file() -> Result<IniDocument, String> {
+    Ok(IniDocument::parse(&read_file(
+        &CONFIG_DIR.join(INPUT_FILE),
+    )?))
+}
+
+pub(crate) fn write_input_file(document: &IniDocument) -> Result<(), String> {
+    write_config_file(&CONFIG_DIR.join(INPUT_FILE), &document.to_string())
+}
@@ -0,0 +1,327 @@
+use super::data::CONFIG_FILES;
+use super::ini_document::IniDocument;
+use super::key_bindings::{
+    find_binding_lines, read_input_file, resolve_conflicts, section_bindings, write_input_file,
+    KeyBinding, BINDINGS_KEY, INPUT_FILE,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+use tauri::{path::BaseDirectory, AppHandle, Manager};
+
+/// Prefixes of alias names, which are bound to commands rather than keys.
+const ALIAS_PREFIXES: [&str; 2] = ["GBA_", "MOBILE_"];
+
+/// Prefixes of controller keys, which get their own cheat sheet category.
+const CONTROLLER_PREFIXES: [&str; 2] = ["XboxTypeS_", "SIXAXIS_"];
+
+/// Version written to new keybind profiles.
+const PROFILE_VERSION: u32 = 1;
+
+/// The section with the player's own bindings, used for the cheat sheet by default.
+const PLAYER_INPUT_SECTION: &str = "Engine.PlayerInput";
+
+/// Cheat sheet categories in the order they are shown, with the words in a command that put
+/// a binding in them. A binding goes in the first category that matches.
+const CATEGORIES: [(&str, &[&str]); 8] = [
+    (
+        "Movement",
+        &[
+            "move", "backward", "strafe", "jump", "ski", "jet", "duck", "turn", "fly", "aup",
+        ],
+    ),
+    (
+        "Weapons",
+        &["fire", "reload", "weapon", "zoom", "laser", "melee"],
+    ),
+    (
+        "Equipment",
+        &["pack", "belt", "deployable", "callin", "translocator"],
+    ),
+    ("Classes and loadouts", &["class", "loadout"]),
+    ("Vehicles", &["seat", "vehicle"]),
+    (
+        "Communication",
+        &[
+            "talk", "chat", "vgs", "speaking", "taunt", "vote", "spot", "horn",
+        ],
+    ),
+    (
+        "Menus and HUD",
+        &[
+            "menu", "score", "map", "settings", "friends", "hud", "marker", "equip",
+        ],
+    ),
+    ("Camera", &["look", "view", "cam"]),
+];
+
+/// A binding in a keybind profile, with the section it belongs to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileBinding {
+    section: String,
+    #[serde(flatten)]
+    binding: KeyBinding,
+}
+
+/// The player's bindings that differ from the launcher's default TribesInput.ini.
+///
+/// Rebinding a default key shows up as the default binding in `removed` and the new one in
+/// `added`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct KeybindProfile {
+    version: u32,
+    added: Vec<ProfileBinding>,
+    removed: Vec<ProfileBinding>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct KeybindExport {
+    /// Bindings in the profile, added and removed
+    bindings: usize,
+    profile_path: String,
+    cheat_sheet_path: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct KeybindImport {
+    added: usize,
+    removed: usize,
+    /// Bindings in the profile that were already applied
+    unchanged: usize,
+}
+
+/// Export the player's non-default bindings to a JSON profile, with a Markdown cheat sheet
+/// of all their bindings next to it.
+///
+/// # Arguments
+///
+/// * `handle` - The AppHandle object
+/// * `path` - The file to write the profile to, the cheat sheet gets the same name with `.md`
+///
+/// # Returns
+///
+/// * `Result<KeybindExport, String>` - Where the files were written or an error message
+#[tauri::command]
+pub fn export_keybinds(handle: AppHandle, path: String) -> Result<KeybindExport, String> {
+    let document = read_input_file()?;
+    let profile = diff_bindings(&read_default_input_file(&handle)?, &document);
+
+    let profile_path = PathBuf::from(path);
+    let content = serde_json::to_string_pretty(&profile).map_err(|e| e.to_string())?;
+    fs::write(&profile_path, content)
+        .map_err(|e| format!("Failed to write keybind profile: {}", e))?;
+
+    let cheat_sheet_path = profile_path.with_extension("md");
+    fs::write(
+        &cheat_sheet_path,
+        render_cheat_sheet(&document, PLAYER_INPUT_SECTION),
+    )
+    .map_err(|e| format!("Failed to write cheat sheet: {}", e))?;
+
+    Ok(KeybindExport {
+        bindings: profile.added.len() + profile.removed.len(),
+        profile_path: profile_path.to_string_lossy().to_string(),
+        cheat_sheet_path: cheat_sheet_path.to_string_lossy().to_string(),
+    })
+}
+
+/// Apply a keybind profile to the player's TribesInput.ini.
+///
+/// Bindings the profile removes are removed if present, then its added bindings are added,
+/// replacing any binding on the same key and modifiers.
+///
+/// # Arguments
+///
+/// * `path` - The profile file
+///
+/// # Returns
+///
+/// * `Result<KeybindImport, String>` - What changed or an error message
+#[tauri::command]
+pub fn import_keybinds(path: String) -> Result<KeybindImport, String> {
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let profile: KeybindProfile =
+        serde_json::from_str(&content).map_err(|e| format!("Invalid keybind profile: {}", e))?;
+    if profile.version > PROFILE_VERSION {
+        return Err(format!(
+            "Keybind profile version {} is newer than this launcher supports",
+            profile.version
+        ));
+    }
+
+    // Check every binding first so a bad profile changes nothing
+    for ProfileBinding { section, binding } in profile.added.iter().chain(&profile.removed) {
+        if section.trim().is_empty() || section.contains(['[', ']', '\n', '\r']) {
+            return Err(format!("Invalid section in keybind profile: {}", section));
+        }
+        binding
+            .validate()
+            .map_err(|e| format!("Invalid binding in keybind profile: {}", e))?;
+    }
+
+    let mut document = read_input_file()?;
+    let mut result = KeybindImport {
+        added: 0,
+        removed: 0,
+        unchanged: 0,
+    };
+
+    for ProfileBinding { section, binding } in &profile.removed {
+        let lines = find_binding_lines(&document, section, binding);
+        if lines.is_empty() {
+            result.unchanged += 1;
+        }
+        for line in lines {
+            result.removed += document.remove(section, BINDINGS_KEY, Some(&line));
+        }
+    }
+
+    for ProfileBinding { section, binding } in &profile.added {
+        if !find_binding_lines(&document, section, binding).is_empty() {
+            result.unchanged += 1;
+            continue;
+        }
+        resolve_conflicts(&mut document, section, binding, None, true)?;
+        document.insert(section, BINDINGS_KEY, &binding.to_string());
+        result.added += 1;
+    }
+
+    write_input_file(&document)?;
+    Ok(result)
+}
+
+/// Render the player's bindings in a section as a Markdown cheat sheet grouped by category.
+///
+/// # Arguments
+///
+/// * `section` - The section to list, defaults to `Engine.PlayerInput`
+///
+/// # Returns
+///
+/// * `Result<String, String>` - The cheat sheet or an error message
+#[tauri::command]
+pub fn render_keybind_cheat_sheet(section: Option<String>) -> Result<String, String> {
+    let section = section.unwrap_or_else(|| PLAYER_INPUT_SECTION.to_string());
+    Ok(render_cheat_sheet(&read_input_file()?, &section))
+}
+
+/// The bindings in `document` that are not in `defaults` and the other way round.
+fn diff_bindings(defaults: &IniDocument, document: &IniDocument) -> KeybindProfile {
+    let mut sections: Vec<&str> = document.sections();
+    for section in defaults.sections() {
+        if !sections.iter().any(|s| s.eq_ignore_ascii_case(section)) {
+            sections.push(section);
+        }
+    }
+
+    let mut profile = KeybindProfile {
+        version: PROFILE_VERSION,
+        added: Vec::new(),
+        removed: Vec::new(),
+    };
+    for section in sections {
+        let mut default_bindings = section_bindings(defaults, section);
+        let mut bindings = section_bindings(document, section);
+
+        // Pair off identical bindings, what is left over differs
+        bindings.retain(
+            |binding| match default_bindings.iter().position(|d| d == binding) {
+                Some(index) => {
+                    default_bindings.remove(index);
+                    false
+                }
+                None => true,
+            },
+        );
+
+        let tag = |binding| ProfileBinding {
+            section: section.to_string(),
+            binding,
+        };
+        profile.added.extend(bindings.into_iter().map(&tag));
+        profile
+            .removed
+            .extend(default_bindings.into_iter().map(&tag));
+    }
+    profile
+}
+
+fn render_cheat_sheet(document: &IniDocument, section: &str) -> String {
+    let bindings = section_bindings(document, section);
+
+    // Bindings named after something another binding runs are aliases, not keys
+    let is_alias = |name: &str| ALIAS_PREFIXES.iter().any(|prefix| name.starts_with(prefix));
+    let aliases: Vec<String> = bindings
+        .iter()
+        .flat_map(|b| b.command.split('|'))
+        .filter_map(|command| command.split_whitespace().next())
+        .map(str::to_lowercase)
+        .collect();
+    let keys: Vec<&KeyBinding> = bindings
+        .iter()
+        .filter(|b| !b.command.trim().is_empty())
+        .filter(|b| !is_alias(&b.name) && !aliases.contains(&b.name.to_lowercase()))
+        .collect();
+
+    let mut groups: Vec<(&str, Vec<&KeyBinding>)> = CATEGORIES
+        .iter()
+        .map(|(category, _)| (*category, Vec::new()))
+        .chain([("Other", Vec::new()), ("Controller", Vec::new())])
+        .collect();
+    for binding in keys {
+        let command = binding.command.to_lowercase();
+        let index = if CONTROLLER_PREFIXES
+            .iter()
+            .any(|prefix| binding.name.starts_with(prefix))
+        {
+            CATEGORIES.len() + 1
+        } else {
+            CATEGORIES
+                .iter()
+                .position(|(_, words)| words.iter().any(|word| command.contains(word)))
+                .unwrap_or(CATEGORIES.len())
+        };
+        groups[index].1.push(binding);
+    }
+
+    let mut sheet = format!("# Key bindings ({})\n", section);
+    for (category, bindings) in groups.iter().filter(|(_, b)| !b.is_empty()) {
+        let _ = write!(sheet, "\n## {}\n\n", category);
+        for binding in bindings {
+            let _ = writeln!(
+                sheet,
+                "- **{}**: {}",
+                binding.keys_label(),
+                command_label(&binding.command)
+            );
+        }
+    }
+    sheet
+}
+
+/// A command without the game's `GBA_` prefixes and empty steps.
+fn command_label(command: &str) -> String {
+    command
+        .split('|')
+        .map(|step| step.trim().replace("GBA_", ""))
+        .filter(|step| !step.is_empty())
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
+fn read_default_input_file(handle: &AppHandle) -> Result<IniDocument, String> {
+    let default_path = CONFIG_FILES
+        .iter()
+        .find(|file| file.name == INPUT_FILE)
+        .ok_or("No default input file")?
+        .default_path;
+    let path = handle
+        .path()
+        .resolve(default_path, BaseDirectory::Resource)
+        .map_err(|e| e.to_string())?;
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    Ok(IniDocument::parse(&content))
+}
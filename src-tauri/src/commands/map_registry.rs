@@ -0,0 +1,193 @@
+use super::data::get_app_local_data_dir;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use tauri::{path::BaseDirectory, AppHandle, Manager};
+
+/// The map registry shipped with the launcher.
+const BUNDLED_MAPS_FILE: &str = "../public/maps/maps.json";
+
+/// File in the app local data directory with the user's own map entries.
+const USER_MAPS_FILE: &str = "maps.json";
+
+/// The locations of both flag stands, in decoded route coordinates.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlagStands {
+    pub(crate) be: (f32, f32, f32),
+    pub(crate) ds: (f32, f32, f32),
+}
+
+/// What the launcher knows about a map's geometry.
+///
+/// Any of the geometry can be left out when it is not known, maps without a mirror centre
+/// are mirrored around the origin.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MapInfo {
+    pub(crate) display_name: String,
+    /// The axis to mirror routes on (`x`, `y` or `xy`), if known
+    pub(crate) mirror_axis: Option<String>,
+    /// The point routes are mirrored around, in decoded route coordinates, if known
+    pub(crate) mirror_centre: Option<(f32, f32)>,
+    pub(crate) flag_stands: Option<FlagStands>,
+}
+
+/// Get every known map, with the user's entries replacing bundled ones.
+///
+/// # Arguments
+///
+/// * `handle` - The AppHandle object
+///
+/// # Returns
+///
+/// * `Result<HashMap<String, MapInfo>, String>` - The maps keyed by route `map_name` or an
+///   error message
+#[tauri::command]
+pub fn get_map_registry(handle: AppHandle) -> Result<HashMap<String, MapInfo>, String> {
+    let mut maps = read_bundled_maps(&handle)?;
+    maps.extend(read_user_maps(&handle)?);
+    Ok(maps)
+}
+
+/// Add or replace a map in the user's registry.
+///
+/// # Arguments
+///
+/// * `handle` - The AppHandle object
+/// * `map_name` - The map name as it appears in route headers
+/// * `info` - The map's geometry
+///
+/// # Returns
+///
+/// * `Result<(), String>` - Ok if the map was saved or an error message
+#[tauri::command]
+pub fn set_map_info(handle: AppHandle, map_name: String, info: MapInfo) -> Result<(), String> {
+    if let Some(axis) = &info.mirror_axis {
+        if !matches!(axis.as_str(), "x" | "y" | "xy" | "yx") {
+            return Err(format!("Invalid mirror axis: {}", axis));
+        }
+    }
+    if info
+        .mirror_centre
+        .is_some_and(|(x, y)| !x.is_finite() || !y.is_finite())
+    {
+        return Err("Mirror centre must be a finite point".into());
+    }
+    if let Some(stands) = &info.flag_stands {
+        let (be, ds) = (stands.be, stands.ds);
+        if [be.0, be.1, be.2, ds.0, ds.1, ds.2]
+            .iter()
+            .any(|v| !v.is_finite())
+        {
+            return Err("Flag stands must be finite points".into());
+        }
+    }
+
+    let mut maps = read_user_maps(&handle)?;
+    maps.insert(map_name, info);
+    write_user_maps(&handle, &maps)
+}
+
+/// Remove a map from the user's registry, falling back to the bundled entry if there is one.
+///
+/// # Arguments
+///
+/// * `handle` - The AppHandle object
+/// * `map_name` - The map name as it appears in route headers
+///
+/// # Returns
+///
+/// * `Result<(), String>` - Ok if the map was removed or an error message
+#[tauri::command]
+pub fn remove_map_info(handle: AppHandle, map_name: String) -> Result<(), String> {
+    let mut maps = read_user_maps(&handle)?;
+    if maps.remove(&map_name).is_none() {
+        return Err(format!("{} is not in your map registry", map_name));
+    }
+    write_user_maps(&handle, &maps)
+}
+
+/// Look up a map by the name in a route header or file name.
+///
+/// Names are matched ignoring case, spaces and any game mode prefix, so `CTF-Arx Novena`
+/// finds `ArxNovena`.
+pub(crate) fn find_map_info(handle: &AppHandle, map_name: &str) -> Option<MapInfo> {
+    let key = map_key(map_name);
+    let find = |maps: HashMap<String, MapInfo>| {
+        maps.into_iter()
+            .find(|(name, _)| map_key(name) == key)
+            .map(|(_, info)| info)
+    };
+
+    // The user's entries win over bundled ones
+    read_user_maps(handle)
+        .ok()
+        .and_then(find)
+        .or_else(|| read_bundled_maps(handle).ok().and_then(find))
+}
+
+fn map_key(map_name: &str) -> String {
+    let name = map_name.rsplit_once('-').map_or(map_name, |(_, name)| name);
+    name.chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn read_bundled_maps(handle: &AppHandle) -> Result<HashMap<String, MapInfo>, String> {
+    let path = handle
+        .path()
+        .resolve(BUNDLED_MAPS_FILE, BaseDirectory::Resource)
+        .map_err(|e| e.to_string())?;
+    read_maps_file(&path)
+}
+
+fn read_user_maps(handle: &AppHandle) -> Result<HashMap<String, MapInfo>, String> {
+    let path = get_app_local_data_dir(handle).join(USER_MAPS_FILE);
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+    read_maps_file(&path)
+}
+
+fn read_maps_file(path: &Path) -> Result<HashMap<String, MapInfo>, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid map registry {}: {}", path.display(), e))
+}
+
+fn write_user_maps(handle: &AppHandle, maps: &HashMap<String, MapInfo>) -> Result<(), String> {
+    let app_data_dir = get_app_local_data_dir(handle);
+    fs::create_dir_all(&app_data_dir)
+        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+
+    let content = serde_json::to_string_pretty(maps).map_err(|e| e.to_string())?;
+    fs::write(app_data_dir.join(USER_MAPS_FILE), content)
+        .map_err(|e| format!("Failed to save map registry: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUNDLED_MAPS: &str = include_str!("../../../public/maps/maps.json");
+
+    #[test]
+    fn bundled_maps_parse_with_missing_geometry() {
+        let maps: HashMap<String, MapInfo> = serde_json::from_str(BUNDLED_MAPS).unwrap();
+        let arx = &maps["ArxNovena"];
+        assert_eq!(arx.display_name, "Arx Novena");
+        assert_eq!(arx.mirror_axis.as_deref(), Some("xy"));
+        assert!(arx.mirror_centre.is_none() && arx.flag_stands.is_none());
+        assert!(maps.values().all(|map| !map.display_name.is_empty()));
+    }
+
+    #[test]
+    fn map_names_match_ignoring_case_spaces_and_game_mode() {
+        assert_eq!(map_key("CTF-Arx Novena"), map_key("ArxNovena"));
+        assert_eq!(map_key("katabatic"), map_key("Katabatic"));
+        assert_ne!(map_key("Katabatic"), map_key("Crossfire"));
+    }
+}
@@ -1,11 +1,33 @@
 pub mod config_backup_manager;
+pub mod config_diff;
 pub mod config_manager;
 pub mod config_preset_manager;
 pub mod data;
 pub mod directory_shortcuts;
 pub mod fetch_player_counts;
 pub mod find_game_path;
+pub mod ini_document;
+pub mod key_bindings;
+pub mod keybind_profile;
 pub mod launch_game;
+pub mod map_registry;
 pub mod package_downloader;
 pub mod packages;
+pub mod personal_bests;
+pub mod route_batch;
+pub mod route_bundle;
+pub mod route_compare;
+pub mod route_editor;
+pub mod route_export;
+pub mod route_heatmap;
+pub mod route_index;
+pub mod route_names;
+pub mod route_playback;
+pub mod route_preview;
+pub mod route_simplify;
+pub mod route_stats;
+pub mod route_trash;
+pub mod route_validation;
+pub mod route_watcher;
 pub mod routes;
+pub mod settings_schema;
@@ -0,0 +1,229 @@
+use super::data::get_app_local_data_dir;
+use super::route_index::refresh_route_index;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+/// File in the app local data directory that holds the personal bests and route history.
+const PERSONAL_BESTS_FILE: &str = "personal_bests.json";
+
+/// The in-memory copy of the personal bests, loaded from disk on first use.
+static PERSONAL_BESTS: Lazy<Mutex<Option<PersonalBests>>> = Lazy::new(|| Mutex::new(None));
+
+/// A route run as recorded in the history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteRecord {
+    file_name: String,
+    game_mode: String,
+    map: String,
+    side: String,
+    class: String,
+    username: String,
+    route_name: String,
+    /// The route time in seconds
+    time: f32,
+    /// When the route file was last written, in milliseconds since the Unix epoch
+    recorded: u64,
+}
+
+impl RouteRecord {
+    /// The map, side, class and player, to sort personal bests by.
+    fn category(&self) -> (&str, &str, &str, &str) {
+        (&self.map, &self.side, &self.class, &self.username)
+    }
+
+    fn same_category(&self, map: &str, side: &str, class: &str) -> bool {
+        self.map.eq_ignore_ascii_case(map)
+            && self.side.eq_ignore_ascii_case(side)
+            && self.class.eq_ignore_ascii_case(class)
+    }
+}
+
+/// The fastest run of a player on a map, side and class.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersonalBest {
+    record: RouteRecord,
+    /// The best time before this one, if there was one
+    previous_time: Option<f32>,
+}
+
+/// Every route seen by the launcher, kept even after the route file is deleted.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PersonalBests {
+    bests: Vec<PersonalBest>,
+    history: HashMap<String, RouteRecord>,
+}
+
+/// Get the personal best for every player, map, side and class.
+///
+/// # Arguments
+///
+/// * `handle` - The AppHandle object
+///
+/// # Returns
+///
+/// * `Result<Vec<PersonalBest>, String>` - The personal bests or an error message
+#[tauri::command]
+pub async fn get_personal_bests(handle: AppHandle) -> Result<Vec<PersonalBest>, String> {
+    tokio::task::spawn_blocking(move || personal_bests(&handle))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+fn personal_bests(handle: &AppHandle) -> Result<Vec<PersonalBest>, String> {
+    update_personal_bests(handle)?;
+
+    let guard = PERSONAL_BESTS
+        .lock()
+        .map_err(|_| "Personal bests lock poisoned".to_string())?;
+    let mut bests: Vec<PersonalBest> = guard
+        .iter()
+        .flat_map(|personal_bests| personal_bests.bests.iter())
+        .cloned()
+        .collect();
+    bests.sort_by(|a, b| a.record.category().cmp(&b.record.category()));
+
+    Ok(bests)
+}
+
+/// Get every recorded run on a map, side and class, oldest first.
+///
+/// # Arguments
+///
+/// * `handle` - The AppHandle object
+/// * `map` - The map name as it appears in route file names
+/// * `side` - `DS` or `BE`
+/// * `class` - The class abbreviation (e.g. `LGT`)
+///
+/// # Returns
+///
+/// * `Result<Vec<RouteRecord>, String>` - The runs or an error message
+#[tauri::command]
+pub async fn get_route_history(
+    handle: AppHandle,
+    map: String,
+    side: String,
+    class: String,
+) -> Result<Vec<RouteRecord>, String> {
+    tokio::task::spawn_blocking(move || route_history(&handle, &map, &side, &class))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+fn route_history(
+    handle: &AppHandle,
+    map: &str,
+    side: &str,
+    class: &str,
+) -> Result<Vec<RouteRecord>, String> {
+    update_personal_bests(handle)?;
+
+    let guard = PERSONAL_BESTS
+        .lock()
+        .map_err(|_| "Personal bests lock poisoned".to_string())?;
+    let mut history: Vec<RouteRecord> = guard
+        .iter()
+        .flat_map(|personal_bests| personal_bests.history.values())
+        .filter(|record| record.same_category(map, side, class))
+        .cloned()
+        .collect();
+    history.sort_by(|a, b| {
+        a.recorded
+            .cmp(&b.recorded)
+            .then_with(|| a.file_name.cmp(&b.file_name))
+    });
+
+    Ok(history)
+}
+
+/// Add routes that are not in the history yet and update the personal bests.
+///
+/// # Returns
+///
+/// * `Result<Vec<PersonalBest>, String>` - The personal bests that were set or beaten
+pub(crate) fn update_personal_bests(handle: &AppHandle) -> Result<Vec<PersonalBest>, String> {
+    let entries = refresh_route_index(handle)?;
+    let path = get_app_local_data_dir(handle).join(PERSONAL_BESTS_FILE);
+
+    let mut guard = PERSONAL_BESTS
+        .lock()
+        .map_err(|_| "Personal bests lock poisoned".to_string())?;
+    let personal_bests = guard.get_or_insert_with(|| load_personal_bests(&path));
+
+    let mut new_records: Vec<RouteRecord> = entries
+        .iter()
+        .filter(|entry| !personal_bests.history.contains_key(&entry.file_name))
+        .filter_map(|entry| {
+            let route = entry.route.as_ref()?;
+            Some(RouteRecord {
+                file_name: entry.file_name.clone(),
+                game_mode: route.game_mode.clone(),
+                map: route.map.clone(),
+                side: route.side.clone(),
+                class: route.class.clone(),
+                username: route.username.clone(),
+                route_name: route.route_name.clone(),
+                time: entry.time().filter(|time| time.is_finite())?,
+                recorded: entry.modified,
+            })
+        })
+        .collect();
+    if new_records.is_empty() {
+        return Ok(Vec::new());
+    }
+    new_records.sort_by_key(|record| record.recorded);
+
+    let mut improved = Vec::new();
+    for record in new_records {
+        personal_bests
+            .history
+            .insert(record.file_name.clone(), record.clone());
+
+        let current = personal_bests.bests.iter_mut().find(|best| {
+            best.record
+                .same_category(&record.map, &record.side, &record.class)
+                && best.record.username.eq_ignore_ascii_case(&record.username)
+        });
+        let best = match current {
+            Some(best) if record.time < best.record.time => {
+                best.previous_time = Some(best.record.time);
+                best.record = record;
+                best.clone()
+            }
+            Some(_) => continue,
+            None => {
+                let best = PersonalBest {
+                    record,
+                    previous_time: None,
+                };
+                personal_bests.bests.push(best.clone());
+                best
+            }
+        };
+        improved.push(best);
+    }
+
+    save_personal_bests(&path, personal_bests)?;
+    Ok(improved)
+}
+
+/// Load the saved personal bests, starting over if they are missing or unreadable.
+fn load_personal_bests(path: &Path) -> PersonalBests {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_personal_bests(path: &Path, personal_bests: &PersonalBests) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+    }
+    let content = serde_json::to_string(personal_bests).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| format!("Failed to save personal bests: {}", e))
+}
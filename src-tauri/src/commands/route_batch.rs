@@ -0,0 +1,315 @@
+use super::data::{move_file, CONFIG_DIR};
+use super::route_export::export_route;
+use super::route_names::parse_route_file_name;
+use super::route_trash::{move_route_to_trash, purge_route_trash};
+use super::routes::{get_route_files, mirror_route, Route};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter};
+
+/// The routes a batch command works on.
+///
+/// Filters are case-insensitive exact matches on the parsed file name. When `files` is
+/// given only those routes are used, narrowed further by any filters.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct RouteSelection {
+    files: Vec<String>,
+    game_mode: Option<String>,
+    map: Option<String>,
+    side: Option<String>,
+    class: Option<String>,
+    username: Option<String>,
+}
+
+impl RouteSelection {
+    fn has_filters(&self) -> bool {
+        [
+            &self.game_mode,
+            &self.map,
+            &self.side,
+            &self.class,
+            &self.username,
+        ]
+        .iter()
+        .any(|filter| filter.is_some())
+    }
+
+    fn matches(&self, route: &Route) -> bool {
+        let is = |value: &str, filter: &Option<String>| {
+            filter
+                .as_ref()
+                .is_none_or(|filter| value.eq_ignore_ascii_case(filter))
+        };
+
+        is(&route.game_mode, &self.game_mode)
+            && is(&route.map, &self.map)
+            && is(&route.side, &self.side)
+            && is(&route.class, &self.class)
+            && is(&route.username, &self.username)
+    }
+}
+
+/// What happened to one route in a batch.
+#[derive(Debug, Serialize)]
+pub struct BatchFileResult {
+    file_name: String,
+    /// The file the route was written or moved to, if the operation made one
+    output: Option<String>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BatchResult {
+    operation: String,
+    succeeded: usize,
+    failed: usize,
+    results: Vec<BatchFileResult>,
+}
+
+/// Progress of a running batch, emitted after each route.
+#[derive(Debug, Clone, Serialize)]
+struct BatchProgress {
+    operation: String,
+    file_name: String,
+    done: usize,
+    total: usize,
+}
+
+/// Move the selected routes to the route trash, then purge routes that have been in the
+/// trash too long.
+///
+/// # Arguments
+///
+/// * `handle` - The AppHandle object
+/// * `selection` - The routes to delete
+///
+/// # Returns
+///
+/// * `Result<BatchResult, String>` - The result for each route or an error message if the
+///   selection is invalid
+///
+/// # Emit
+///
+/// * `route-batch-progress` - After each route, with the `BatchProgress`
+#[tauri::command]
+pub async fn batch_delete_routes(
+    handle: AppHandle,
+    selection: RouteSelection,
+) -> Result<BatchResult, String> {
+    let files = select_routes(&selection)?;
+    let trash_handle = handle.clone();
+    let result = spawn_batch(handle.clone(), "delete", files, move |file| {
+        move_route_to_trash(&trash_handle, file).map(|_| None)
+    })
+    .await?;
+
+    // Purge once for the whole batch rather than after every route
+    if let Err(e) = purge_route_trash(&handle) {
+        warn!("Failed to purge route trash: {}", e);
+    }
+    Ok(result)
+}
+
+/// Mirror the selected routes to the other side.
+///
+/// # Arguments
+///
+/// * `handle` - The AppHandle object
+/// * `selection` - The routes to mirror
+/// * `axis` - The axis to mirror across, see `mirror_route`
+///
+/// # Returns
+///
+/// * `Result<BatchResult, String>` - The result for each route or an error message if the
+///   selection is invalid
+///
+/// # Emit
+///
+/// * `route-batch-progress` - After each route, with the `BatchProgress`
+#[tauri::command]
+pub async fn batch_mirror_routes(
+    handle: AppHandle,
+    selection: RouteSelection,
+    axis: Option<String>,
+) -> Result<BatchResult, String> {
+    let files = select_routes(&selection)?;
+    let mirror_handle = handle.clone();
+    spawn_batch(handle, "mirror", files, move |file| {
+        mirror_route(mirror_handle.clone(), file.to_string(), axis.clone()).map(Some)
+    })
+    .await
+}
+
+/// Export the selected routes into a directory, one file per route.
+///
+/// # Arguments
+///
+/// * `handle` - The AppHandle object
+/// * `selection` - The routes to export
+/// * `format` - The format to export to, see `export_route`
+/// * `directory` - The directory to write the exported routes to
+///
+/// # Returns
+///
+/// * `Result<BatchResult, String>` - The result for each route or an error message if the
+///   selection or directory is invalid
+///
+/// # Emit
+///
+/// * `route-batch-progress` - After each route, with the `BatchProgress`
+#[tauri::command]
+pub async fn batch_export_routes(
+    handle: AppHandle,
+    selection: RouteSelection,
+    format: String,
+    directory: String,
+) -> Result<BatchResult, String> {
+    let files = select_routes(&selection)?;
+    let directory = PathBuf::from(directory);
+    fs::create_dir_all(&directory)
+        .map_err(|e| format!("Failed to create {}: {}", directory.display(), e))?;
+
+    let extension = format.to_lowercase();
+    spawn_batch(handle, "export", files, move |file| {
+        let exported = export_route(file.to_string(), format.clone())?;
+        let stem = file.strip_suffix(".route").unwrap_or(file);
+        let target = directory.join(format!("{}.{}", stem, extension));
+        fs::write(&target, exported).map_err(|e| format!("Failed to write export: {}", e))?;
+        Ok(Some(target.to_string_lossy().to_string()))
+    })
+    .await
+}
+
+/// Move the selected routes out of the routes directory into another directory.
+///
+/// # Arguments
+///
+/// * `handle` - The AppHandle object
+/// * `selection` - The routes to move
+/// * `directory` - The directory to move the routes to
+///
+/// # Returns
+///
+/// * `Result<BatchResult, String>` - The result for each route or an error message if the
+///   selection or directory is invalid
+///
+/// # Emit
+///
+/// * `route-batch-progress` - After each route, with the `BatchProgress`
+#[tauri::command]
+pub async fn batch_move_routes(
+    handle: AppHandle,
+    selection: RouteSelection,
+    directory: String,
+) -> Result<BatchResult, String> {
+    let files = select_routes(&selection)?;
+    let directory = PathBuf::from(directory);
+    fs::create_dir_all(&directory)
+        .map_err(|e| format!("Failed to create {}: {}", directory.display(), e))?;
+    if is_same_dir(&directory, &CONFIG_DIR.join("routes")) {
+        return Err("Routes are already in that directory".into());
+    }
+
+    spawn_batch(handle, "move", files, move |file| {
+        let source = CONFIG_DIR.join("routes").join(file);
+        if !source.is_file() {
+            return Err(format!("File {} does not exist", file));
+        }
+        let target = directory.join(file);
+        if target.exists() {
+            return Err(format!("{} already exists", target.display()));
+        }
+        move_file(&source, &target).map_err(|e| format!("Failed to move route: {}", e))?;
+        Ok(Some(target.to_string_lossy().to_string()))
+    })
+    .await
+}
+
+/// The route file names in a selection.
+fn select_routes(selection: &RouteSelection) -> Result<Vec<String>, String> {
+    if selection.files.is_empty() && !selection.has_filters() {
+        return Err("Select routes by file name or at least one filter".into());
+    }
+
+    if selection.files.is_empty() {
+        return Ok(get_route_files()?
+            .routes
+            .into_iter()
+            .filter(|route| selection.matches(route))
+            .map(|route| route.file_name)
+            .collect());
+    }
+
+    Ok(selection
+        .files
+        .iter()
+        .filter(|file| {
+            !selection.has_filters()
+                || parse_route_file_name(file).is_ok_and(|route| selection.matches(&route))
+        })
+        .cloned()
+        .collect())
+}
+
+/// Run an operation over every route off the main thread.
+async fn spawn_batch(
+    handle: AppHandle,
+    operation: &'static str,
+    files: Vec<String>,
+    run: impl Fn(&str) -> Result<Option<String>, String> + Send + 'static,
+) -> Result<BatchResult, String> {
+    tokio::task::spawn_blocking(move || run_batch(&handle, operation, files, run))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+fn run_batch(
+    handle: &AppHandle,
+    operation: &str,
+    files: Vec<String>,
+    run: impl Fn(&str) -> Result<Option<String>, String>,
+) -> BatchResult {
+    let total = files.len();
+    let mut results = Vec::with_capacity(total);
+
+    for (index, file_name) in files.into_iter().enumerate() {
+        let (output, error) = match run(&file_name) {
+            Ok(output) => (output, None),
+            Err(e) => (None, Some(e)),
+        };
+
+        let progress = BatchProgress {
+            operation: operation.to_string(),
+            file_name: file_name.clone(),
+            done: index + 1,
+            total,
+        };
+        if let Err(e) = handle.emit("route-batch-progress", progress) {
+            warn!("Failed to emit route-batch-progress: {}", e);
+        }
+
+        results.push(BatchFileResult {
+            file_name,
+            output,
+            error,
+        });
+    }
+
+    let failed = results.iter().filter(|r| r.error.is_some()).count();
+    BatchResult {
+        operation: operation.to_string(),
+        succeeded: total - failed,
+        failed,
+        results,
+    }
+}
+
+fn is_same_dir(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
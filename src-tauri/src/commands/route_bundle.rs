@@ -0,0 +1,177 @@
+use super::data::CONFIG_DIR;
+use super::route_index::RouteHeader;
+use super::route_names::parse_route_file_name;
+use super::routes::{read_route, Route};
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+/// Name of the manifest inside a route bundle.
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// Version of the manifest layout, bumped if it changes.
+const MANIFEST_VERSION: u32 = 1;
+
+/// A route in a bundle manifest.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BundleRoute {
+    file_name: String,
+    sha256: String,
+    route: Option<Route>,
+    header: Option<RouteHeader>,
+}
+
+/// The manifest stored in a route bundle.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BundleManifest {
+    version: u32,
+    created: String,
+    routes: Vec<BundleRoute>,
+}
+
+/// What happened to each route in an imported bundle.
+#[derive(Debug, Default, Serialize)]
+pub struct BundleImportResult {
+    /// Routes copied into the routes directory
+    imported: Vec<String>,
+    /// Routes skipped because an identical file already exists
+    duplicates: Vec<String>,
+    /// Routes skipped because a different route with the same name already exists
+    conflicts: Vec<String>,
+}
+
+/// Pack routes into a zip bundle with a manifest describing them.
+///
+/// # Arguments
+///
+/// * `files` - The route file names in the routes directory
+/// * `path` - Where to write the bundle
+///
+/// # Returns
+///
+/// * `Result<BundleManifest, String>` - The manifest written to the bundle or an error message
+#[tauri::command]
+pub fn export_route_bundle(files: Vec<String>, path: String) -> Result<BundleManifest, String> {
+    let routes_path = CONFIG_DIR.join("routes");
+    let bundle = File::create(&path).map_err(|e| format!("Failed to create {}: {}", path, e))?;
+    let mut zip = ZipWriter::new(bundle);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    let mut routes = Vec::new();
+    for file in files {
+        let content = fs::read(routes_path.join(&file))
+            .map_err(|e| format!("Failed to read route {}: {}", file, e))?;
+
+        zip.start_file(file.as_str(), options)
+            .map_err(|e| e.to_string())?;
+        zip.write_all(&content).map_err(|e| e.to_string())?;
+
+        routes.push(BundleRoute {
+            sha256: content_hash(&content),
+            route: parse_route_file_name(&file).ok(),
+            header: read_route(&mut content.as_slice())
+                .ok()
+                .as_ref()
+                .map(RouteHeader::from),
+            file_name: file,
+        });
+    }
+
+    let manifest = BundleManifest {
+        version: MANIFEST_VERSION,
+        created: Local::now().to_rfc3339(),
+        routes,
+    };
+    let manifest_json = serde_json::to_string_pretty(&manifest).map_err(|e| e.to_string())?;
+    zip.start_file(MANIFEST_FILE, options)
+        .map_err(|e| e.to_string())?;
+    zip.write_all(manifest_json.as_bytes())
+        .map_err(|e| e.to_string())?;
+    zip.finish().map_err(|e| e.to_string())?;
+
+    Ok(manifest)
+}
+
+/// Unpack a route bundle into the routes directory.
+///
+/// Routes whose content already exists under any name are skipped as duplicates, and
+/// routes whose name is taken by a different file are skipped as conflicts.
+///
+/// # Arguments
+///
+/// * `path` - The path to the bundle
+///
+/// # Returns
+///
+/// * `Result<BundleImportResult, String>` - What happened to each route or an error message
+#[tauri::command]
+pub fn import_route_bundle(path: String) -> Result<BundleImportResult, String> {
+    let routes_path = CONFIG_DIR.join("routes");
+    fs::create_dir_all(&routes_path)
+        .map_err(|e| format!("Failed to create routes directory: {}", e))?;
+
+    let bundle = File::open(&path).map_err(|e| format!("Failed to open {}: {}", path, e))?;
+    let mut archive = ZipArchive::new(bundle).map_err(|e| e.to_string())?;
+
+    let mut existing_hashes = existing_route_hashes()?;
+    let mut result = BundleImportResult::default();
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(|e| e.to_string())?;
+
+        // Only take route files, and only their file name so nothing escapes the routes folder
+        let Some(file_name) = entry
+            .enclosed_name()
+            .and_then(|name| name.file_name().map(|n| n.to_string_lossy().to_string()))
+            .filter(|name| name.ends_with(".route"))
+        else {
+            continue;
+        };
+
+        let mut content = Vec::new();
+        entry
+            .read_to_end(&mut content)
+            .map_err(|e| format!("Failed to read {} from bundle: {}", file_name, e))?;
+        let hash = content_hash(&content);
+
+        let target = routes_path.join(&file_name);
+        if existing_hashes.contains(&hash) {
+            result.duplicates.push(file_name);
+        } else if target.exists() {
+            result.conflicts.push(file_name);
+        } else {
+            fs::write(&target, &content)
+                .map_err(|e| format!("Failed to write route {}: {}", file_name, e))?;
+            existing_hashes.insert(hash);
+            result.imported.push(file_name);
+        }
+    }
+
+    Ok(result)
+}
+
+/// The SHA-256 of the content as a hex string.
+pub(crate) fn content_hash(content: &[u8]) -> String {
+    Sha256::digest(content)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+fn existing_route_hashes() -> Result<HashSet<String>, String> {
+    let entries = fs::read_dir(CONFIG_DIR.join("routes"))
+        .map_err(|e| format!("Failed to read routes directory: {}", e))?;
+
+    Ok(entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "route"))
+        .filter_map(|path| fs::read(path).ok())
+        .map(|content| content_hash(&content))
+        .collect())
+}
@@ -0,0 +1,239 @@
+use super::route_stats::{distance, speed};
+use super::routes::{load_route, Position};
+use serde::Serialize;
+use tauri::command;
+
+/// Distance between checkpoints along the base route, in Unreal units.
+const DEFAULT_CHECKPOINT_SPACING: f32 = 500.0;
+
+/// How far ahead along the other route to look for the nearest point, in checkpoints.
+/// Keeps the alignment from jumping to a later part of the path that loops back.
+const SEARCH_LOOKAHEAD: f32 = 4.0;
+
+/// Most checkpoints a comparison can have, so a tiny spacing cannot run away.
+const MAX_CHECKPOINTS: usize = 10_000;
+
+/// A checkpoint on the base route and where the other route passed it.
+///
+/// Deltas are other minus base, so a positive time delta means the other route was slower.
+#[derive(Debug, Serialize)]
+pub struct ComparisonPoint {
+    distance: f32,
+    base_time: f32,
+    other_time: f32,
+    time_delta: f32,
+    speed_delta: f32,
+    energy_delta: f32,
+    separation: f32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RouteComparison {
+    base: String,
+    other: String,
+    points: Vec<ComparisonPoint>,
+    final_time_delta: f32,
+}
+
+/// The interpolated state of a route at some point along its path.
+struct PathPoint {
+    loc: (f32, f32, f32),
+    time: f32,
+    speed: f32,
+    energy: f32,
+}
+
+/// Compare two routes by aligning them spatially.
+///
+/// The base route is split into checkpoints at a fixed distance along its path, and each
+/// checkpoint is matched to the nearest point along the other route's path.
+///
+/// # Arguments
+///
+/// * `base` - The route file name to compare against
+/// * `other` - The route file name to compare
+/// * `checkpoint_spacing` - Distance between checkpoints in Unreal units, defaults to 500
+///
+/// # Returns
+///
+/// * `Result<RouteComparison, String>` - The per-checkpoint deltas or an error message
+#[command]
+pub fn compare_routes(
+    base: String,
+    other: String,
+    checkpoint_spacing: Option<f32>,
+) -> Result<RouteComparison, String> {
+    let spacing = checkpoint_spacing.unwrap_or(DEFAULT_CHECKPOINT_SPACING);
+    if !spacing.is_finite() || spacing <= 0.0 {
+        return Err("Checkpoint spacing must be greater than zero".into());
+    }
+
+    let base_route = load_route(&base)?;
+    let other_route = load_route(&other)?;
+
+    if !base_route
+        .map_name
+        .eq_ignore_ascii_case(&other_route.map_name)
+    {
+        return Err(format!(
+            "Routes are on different maps ({} and {})",
+            base_route.map_name, other_route.map_name
+        ));
+    }
+    if base_route.positions.len() < 2 || other_route.positions.len() < 2 {
+        return Err("Both routes need at least two positions to compare".into());
+    }
+
+    let points = align_routes(&base_route.positions, &other_route.positions, spacing)?;
+    let final_time_delta = points.last().map_or(0.0, |p| p.time_delta);
+
+    Ok(RouteComparison {
+        base,
+        other,
+        points,
+        final_time_delta,
+    })
+}
+
+fn align_routes(
+    base: &[Position],
+    other: &[Position],
+    spacing: f32,
+) -> Result<Vec<ComparisonPoint>, String> {
+    let base_distances = cumulative_distances(base);
+    let other_distances = cumulative_distances(other);
+    let base_total = *base_distances.last().unwrap_or(&0.0);
+    let other_total = *other_distances.last().unwrap_or(&0.0);
+    if !base_total.is_finite() || !other_total.is_finite() {
+        return Err("A route has positions with invalid locations".into());
+    }
+    let (base_start, other_start) = (base[0].time, other[0].time);
+
+    // Checkpoints at every multiple of the spacing, the last one clamped to the path's end
+    let count = (base_total as f64 / spacing as f64).ceil();
+    if count >= MAX_CHECKPOINTS as f64 {
+        return Err(format!(
+            "Checkpoint spacing {} is too small for a route {} units long",
+            spacing, base_total
+        ));
+    }
+
+    let mut points = Vec::new();
+    let mut segment = 0;
+    for index in 0..=count as usize {
+        let checkpoint = (index as f32 * spacing).min(base_total);
+        let base_point = point_at_distance(base, &base_distances, checkpoint);
+
+        // Find the nearest point on the other route, only ever moving forward along it
+        let max_distance = other_distances[segment] + spacing * SEARCH_LOOKAHEAD;
+        let (nearest_segment, other_point) = nearest_point(
+            other,
+            &other_distances,
+            segment,
+            max_distance,
+            base_point.loc,
+        );
+        segment = nearest_segment;
+
+        let base_time = base_point.time - base_start;
+        let other_time = other_point.time - other_start;
+        points.push(ComparisonPoint {
+            distance: checkpoint,
+            base_time,
+            other_time,
+            time_delta: other_time - base_time,
+            speed_delta: other_point.speed - base_point.speed,
+            energy_delta: other_point.energy - base_point.energy,
+            separation: distance(base_point.loc, other_point.loc),
+        });
+    }
+
+    Ok(points)
+}
+
+/// The distance travelled along the path at each sample.
+fn cumulative_distances(positions: &[Position]) -> Vec<f32> {
+    let mut total = 0.0;
+    let mut distances = Vec::with_capacity(positions.len());
+    distances.push(0.0);
+    for pair in positions.windows(2) {
+        total += distance(pair[0].loc, pair[1].loc);
+        distances.push(total);
+    }
+    distances
+}
+
+/// Interpolate the route state at a given distance along its path.
+fn point_at_distance(positions: &[Position], distances: &[f32], target: f32) -> PathPoint {
+    let segment = distances
+        .windows(2)
+        .position(|d| target <= d[1])
+        .unwrap_or(positions.len() - 2);
+    let length = distances[segment + 1] - distances[segment];
+    let fraction = if length > 0.0 {
+        (target - distances[segment]) / length
+    } else {
+        0.0
+    };
+    interpolate(&positions[segment], &positions[segment + 1], fraction)
+}
+
+/// Project a location onto the path, searching segments from `start` until the path
+/// is further along than `max_distance`. Returns the segment index and the point on it.
+fn nearest_point(
+    positions: &[Position],
+    distances: &[f32],
+    start: usize,
+    max_distance: f32,
+    loc: (f32, f32, f32),
+) -> (usize, PathPoint) {
+    let mut best = (start, 0.0, f32::MAX);
+    for segment in start..positions.len() - 1 {
+        if segment > start && distances[segment] > max_distance {
+            break;
+        }
+        let a = positions[segment].loc;
+        let b = positions[segment + 1].loc;
+        let fraction = project(a, b, loc);
+        let projected = (
+            a.0 + (b.0 - a.0) * fraction,
+            a.1 + (b.1 - a.1) * fraction,
+            a.2 + (b.2 - a.2) * fraction,
+        );
+        let gap = distance(projected, loc);
+        if gap < best.2 {
+            best = (segment, fraction, gap);
+        }
+    }
+
+    let (segment, fraction, _) = best;
+    (
+        segment,
+        interpolate(&positions[segment], &positions[segment + 1], fraction),
+    )
+}
+
+/// The fraction along the segment `a`-`b` closest to `loc`, clamped to the segment.
+pub(crate) fn project(a: (f32, f32, f32), b: (f32, f32, f32), loc: (f32, f32, f32)) -> f32 {
+    let ab = (b.0 - a.0, b.1 - a.1, b.2 - a.2);
+    let al = (loc.0 - a.0, loc.1 - a.1, loc.2 - a.2);
+    let length_sq = ab.0 * ab.0 + ab.1 * ab.1 + ab.2 * ab.2;
+    if length_sq == 0.0 {
+        return 0.0;
+    }
+    ((al.0 * ab.0 + al.1 * ab.1 + al.2 * ab.2) / length_sq).clamp(0.0, 1.0)
+}
+
+fn interpolate(a: &Position, b: &Position, fraction: f32) -> PathPoint {
+    let lerp = |x: f32, y: f32| x + (y - x) * fraction;
+    PathPoint {
+        loc: (
+            lerp(a.loc.0, b.loc.0),
+            lerp(a.loc.1, b.loc.1),
+            lerp(a.loc.2, b.loc.2),
+        ),
+        time: lerp(a.time, b.time),
+        speed: lerp(speed(a), speed(b)),
+        energy: lerp(a.energy, b.energy),
+    }
+}
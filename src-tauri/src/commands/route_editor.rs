@@ -0,0 +1,184 @@
+use super::data::{get_app_local_data_dir, CONFIG_DIR};
+use super::route_names::{parse_route_file_name, route_file_name, DEFAULT_GAME_MODE};
+use super::routes::{encode_route_file, load_route, RouteData};
+use chrono::Local;
+use serde::Deserialize;
+use std::fs;
+use tauri::AppHandle;
+
+/// Changes to make to a route. Anything left out is kept as it is.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct RouteEdit {
+    /// Drop positions recorded before this time
+    trim_start: Option<f32>,
+    /// Drop positions recorded after this time
+    trim_end: Option<f32>,
+    description: Option<String>,
+    player_name: Option<String>,
+    /// The new flag grab time, relative to the start of the edited route
+    flag_grab_time: Option<f32>,
+    /// Replace the original file (after backing it up) instead of saving a new one. The
+    /// route is renamed if the edit changes its duration, player name or description.
+    overwrite: bool,
+}
+
+/// Edit a route and save the result.
+///
+/// Trimming re-bases `time` and `eta` so the first kept position is at zero, and the
+/// flag grab time moves with them unless a new one is given.
+///
+/// # Arguments
+///
+/// * `handle` - The AppHandle object
+/// * `file` - The route file name in the routes directory
+/// * `edit` - The changes to make
+///
+/// # Returns
+///
+/// * `Result<String, String>` - The file name the edited route was saved as or an error message
+#[tauri::command]
+pub fn edit_route(handle: AppHandle, file: String, edit: RouteEdit) -> Result<String, String> {
+    let mut route = load_route(&file)?;
+    let game_mode = parse_route_file_name(&file)
+        .map(|r| r.game_mode)
+        .unwrap_or_else(|_| DEFAULT_GAME_MODE.to_string());
+    let original_name = route_file_name(&game_mode, &route);
+    apply_route_edit(&mut route, &edit)?;
+
+    let routes_path = CONFIG_DIR.join("routes");
+    let file_name = edited_file_name(
+        &file,
+        &original_name,
+        route_file_name(&game_mode, &route),
+        edit.overwrite,
+        |name| routes_path.join(name).exists(),
+    )?;
+    if edit.overwrite {
+        backup_route_file(&handle, &file)?;
+    }
+
+    encode_route_file(&routes_path.join(&file_name), &route)
+        .map_err(|e| format!("Failed to write edited route: {}", e))?;
+
+    if edit.overwrite && file_name != file {
+        fs::remove_file(routes_path.join(&file))
+            .map_err(|e| format!("Failed to remove the original route {}: {}", file, e))?;
+    }
+
+    Ok(file_name)
+}
+
+/// The file name to save an edited route as.
+///
+/// The name holds the duration and header strings, so it changes when they do. Only an
+/// overwrite may replace an existing file, and only the route being edited.
+fn edited_file_name(
+    file: &str,
+    original_name: &str,
+    edited_name: String,
+    overwrite: bool,
+    exists: impl Fn(&str) -> bool,
+) -> Result<String, String> {
+    let file_name = if overwrite && edited_name == original_name {
+        file.to_string()
+    } else {
+        edited_name
+    };
+
+    let replaces_original = overwrite && file_name == file;
+    if !replaces_original && exists(&file_name) {
+        return Err(format!("Route {} already exists", file_name));
+    }
+    Ok(file_name)
+}
+
+/// Apply an edit to a decoded route.
+pub(crate) fn apply_route_edit(route: &mut RouteData, edit: &RouteEdit) -> Result<(), String> {
+    if let Some(start) = edit.trim_start {
+        route.positions.retain(|p| p.time >= start);
+    }
+    if let Some(end) = edit.trim_end {
+        route.positions.retain(|p| p.time <= end);
+    }
+    let Some(first) = route.positions.first() else {
+        return Err("No positions left after trimming".into());
+    };
+
+    // Re-base times on the first kept position
+    let (time_offset, eta_offset) = (first.time, first.eta);
+    for position in route.positions.iter_mut() {
+        position.time -= time_offset;
+        position.eta -= eta_offset;
+    }
+    route.flag_grab_time = edit
+        .flag_grab_time
+        .unwrap_or(route.flag_grab_time - time_offset);
+
+    if let Some(description) = &edit.description {
+        route.description = description.clone();
+    }
+    if let Some(player_name) = &edit.player_name {
+        route.player_name = player_name.clone();
+    }
+    route.check_header_strings()?;
+    route.route_length = route.positions.len() as u32;
+
+    Ok(())
+}
+
+/// Copy a route to the route backups in app local data before it is overwritten.
+pub(crate) fn backup_route_file(handle: &AppHandle, file: &str) -> Result<(), String> {
+    let backup_dir = get_app_local_data_dir(handle).join("route_backups");
+    fs::create_dir_all(&backup_dir)
+        .map_err(|e| format!("Failed to create route backup directory: {}", e))?;
+
+    let timestamp = Local::now().format("%Y%m%d%H%M%S");
+    fs::copy(
+        CONFIG_DIR.join("routes").join(file),
+        backup_dir.join(format!("{}_{}", timestamp, file)),
+    )
+    .map_err(|e| format!("Failed to backup route {}: {}", file, e))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILE: &str = "CTF_Katabatic_BE_LGT_Runner_Practice_12.50.route";
+
+    #[test]
+    fn saving_a_copy_never_replaces_an_existing_route() {
+        // An unchanged canonical name is the original file, which exists
+        let result = edited_file_name(FILE, FILE, FILE.to_string(), false, |_| true);
+        assert_eq!(result, Err(format!("Route {} already exists", FILE)));
+
+        let result = edited_file_name(FILE, FILE, "new.route".to_string(), false, |name| {
+            name == FILE
+        });
+        assert_eq!(result.as_deref(), Ok("new.route"));
+    }
+
+    #[test]
+    fn overwriting_keeps_or_renames_the_original() {
+        let exists = |name: &str| name == FILE || name == "taken.route";
+        assert_eq!(
+            edited_file_name(
+                FILE,
+                "canonical.route",
+                "canonical.route".into(),
+                true,
+                exists
+            )
+            .as_deref(),
+            Ok(FILE)
+        );
+        assert_eq!(
+            edited_file_name(FILE, FILE, "shorter.route".into(), true, exists).as_deref(),
+            Ok("shorter.route")
+        );
+        assert!(edited_file_name(FILE, FILE, "taken.route".into(), true, exists).is_err());
+    }
+}
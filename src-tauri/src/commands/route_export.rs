@@ -0,0 +1,342 @@
+use super::data::CONFIG_DIR;
+use super::route_names::{parse_route_file_name, route_file_name, DEFAULT_GAME_MODE};
+use super::routes::{encode_route_file, load_route, Position, RouteData};
+use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, Event};
+use quick_xml::{Reader, Writer};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use tauri::command;
+
+/// Column order of the CSV export, one row per position.
+const CSV_COLUMNS: [&str; 15] = [
+    "time", "loc_x", "loc_y", "loc_z", "vel_x", "vel_y", "vel_z", "pitch", "yaw", "phys", "skiing",
+    "jetting", "health", "energy", "eta",
+];
+
+/// Header fields written ahead of the positions in the CSV and XML exports.
+const HEADER_FIELDS: [&str; 11] = [
+    "game_mode",
+    "route_file_version",
+    "map_name",
+    "class_abbr",
+    "player_name",
+    "description",
+    "team_num",
+    "class_id",
+    "class_health",
+    "flag_grab_time",
+    "route_length",
+];
+
+/// A route together with the game mode from its file name, as written in JSON exports.
+#[derive(Serialize, Deserialize)]
+struct RouteExport {
+    #[serde(default)]
+    game_mode: Option<String>,
+    #[serde(flatten)]
+    route: RouteData,
+}
+
+/// Export a route to a text format.
+///
+/// # Arguments
+///
+/// * `file` - The route file name in the routes directory
+/// * `format` - The format to export to (`json`, `csv` or `xml`)
+///
+/// # Returns
+///
+/// * `Result<String, String>` - The exported route or an error message
+#[command]
+pub fn export_route(file: String, format: String) -> Result<String, String> {
+    let route = load_route(&file)?;
+    let game_mode = parse_route_file_name(&file)
+        .map(|r| r.game_mode)
+        .unwrap_or_else(|_| DEFAULT_GAME_MODE.to_string());
+
+    match format.to_lowercase().as_str() {
+        "json" => serde_json::to_string_pretty(&RouteExport {
+            game_mode: Some(game_mode),
+            route,
+        })
+        .map_err(|e| format!("Failed to serialise route: {}", e)),
+        "csv" => Ok(route_to_csv(&game_mode, &route)),
+        "xml" | "gpx" => route_to_xml(&game_mode, &route),
+        _ => Err(format!("Unsupported export format: {}", format)),
+    }
+}
+
+/// Import a route exported by `export_route` into the routes directory.
+///
+/// The format is picked from the file extension (`.json`, `.csv`, `.xml` or `.gpx`).
+///
+/// # Arguments
+///
+/// * `path` - The path to the exported route
+///
+/// # Returns
+///
+/// * `Result<String, String>` - The file name of the imported route or an error message
+#[command]
+pub fn import_route(path: String) -> Result<String, String> {
+    let source = Path::new(&path);
+    let content =
+        fs::read_to_string(source).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+
+    let extension = source
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or_default()
+        .to_lowercase();
+    let (game_mode, mut route) = match extension.as_str() {
+        "json" => {
+            let export: RouteExport = serde_json::from_str(&content)
+                .map_err(|e| format!("Failed to parse route JSON: {}", e))?;
+            (export.game_mode, export.route)
+        }
+        "csv" => route_from_csv(&content)?,
+        "xml" | "gpx" => route_from_xml(&content)?,
+        _ => return Err(format!("Unsupported import format: {}", extension)),
+    };
+    let game_mode = game_mode.unwrap_or_else(|| DEFAULT_GAME_MODE.to_string());
+    route.check_header_strings()?;
+    route.route_length = route.positions.len() as u32;
+
+    let routes_path = CONFIG_DIR.join("routes");
+    fs::create_dir_all(&routes_path)
+        .map_err(|e| format!("Failed to create routes directory: {}", e))?;
+
+    let file_name = route_file_name(&game_mode, &route);
+    let file_path = routes_path.join(&file_name);
+    if file_path.exists() {
+        return Err(format!("Route {} already exists", file_name));
+    }
+
+    encode_route_file(&file_path, &route)
+        .map_err(|e| format!("Failed to write imported route: {}", e))?;
+
+    Ok(file_name)
+}
+
+/// The header values of a route in the order of `HEADER_FIELDS`.
+fn header_values(game_mode: &str, route: &RouteData) -> [String; 11] {
+    [
+        game_mode.to_string(),
+        route.route_file_version.to_string(),
+        route.map_name.clone(),
+        route.class_abbr.clone(),
+        route.player_name.clone(),
+        route.description.clone(),
+        route.team_num.to_string(),
+        route.class_id.to_string(),
+        route.class_health.to_string(),
+        route.flag_grab_time.to_string(),
+        route.route_length.to_string(),
+    ]
+}
+
+/// The position values in the order of `CSV_COLUMNS`.
+fn position_values(position: &Position) -> [String; 15] {
+    [
+        position.time.to_string(),
+        position.loc.0.to_string(),
+        position.loc.1.to_string(),
+        position.loc.2.to_string(),
+        position.vel.0.to_string(),
+        position.vel.1.to_string(),
+        position.vel.2.to_string(),
+        position.pitch.to_string(),
+        position.yaw.to_string(),
+        position.phys.to_string(),
+        position.skiing.to_string(),
+        position.jetting.to_string(),
+        position.health.to_string(),
+        position.energy.to_string(),
+        position.eta.to_string(),
+    ]
+}
+
+/// Rebuild a route from its header and position fields.
+fn route_from_fields(
+    header: &HashMap<String, String>,
+    positions: &[HashMap<String, String>],
+) -> Result<(Option<String>, RouteData), String> {
+    let positions = positions
+        .iter()
+        .map(|fields| {
+            Ok(Position {
+                time: field(fields, "time")?,
+                loc: (
+                    field(fields, "loc_x")?,
+                    field(fields, "loc_y")?,
+                    field(fields, "loc_z")?,
+                ),
+                vel: (
+                    field(fields, "vel_x")?,
+                    field(fields, "vel_y")?,
+                    field(fields, "vel_z")?,
+                ),
+                pitch: field(fields, "pitch")?,
+                yaw: field(fields, "yaw")?,
+                phys: field(fields, "phys")?,
+                skiing: field(fields, "skiing")?,
+                jetting: field(fields, "jetting")?,
+                health: field(fields, "health")?,
+                energy: field(fields, "energy")?,
+                eta: field(fields, "eta")?,
+                padding: 0,
+                raw_flags: [0; 2],
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let route = RouteData {
+        route_file_version: field(header, "route_file_version")?,
+        map_name: field(header, "map_name")?,
+        class_abbr: field(header, "class_abbr")?,
+        player_name: field(header, "player_name")?,
+        description: field(header, "description")?,
+        team_num: field(header, "team_num")?,
+        class_id: field(header, "class_id")?,
+        class_health: field(header, "class_health")?,
+        flag_grab_time: field(header, "flag_grab_time")?,
+        route_length: field(header, "route_length")?,
+        positions,
+        encoding: Default::default(),
+    };
+
+    Ok((header.get("game_mode").cloned(), route))
+}
+
+/// Parse a named field, reporting which one is missing or invalid.
+fn field<T: FromStr>(fields: &HashMap<String, String>, name: &str) -> Result<T, String> {
+    let value = fields
+        .get(name)
+        .ok_or_else(|| format!("Missing field {}", name))?;
+    value
+        .trim()
+        .parse()
+        .map_err(|_| format!("Invalid value for {}: {}", name, value))
+}
+
+/// Write the route as CSV. The header is stored in `# key=value` lines above the table.
+fn route_to_csv(game_mode: &str, route: &RouteData) -> String {
+    let mut csv = String::new();
+    for (key, value) in HEADER_FIELDS.iter().zip(header_values(game_mode, route)) {
+        csv.push_str(&format!("# {}={}\n", key, value));
+    }
+
+    csv.push_str(&CSV_COLUMNS.join(","));
+    csv.push('\n');
+    for position in &route.positions {
+        csv.push_str(&position_values(position).join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+fn route_from_csv(content: &str) -> Result<(Option<String>, RouteData), String> {
+    let mut header = HashMap::new();
+    let mut columns: Option<Vec<String>> = None;
+    let mut positions = Vec::new();
+
+    for line in content.lines() {
+        let line = line.trim_end_matches('\r');
+        if let Some(meta) = line.strip_prefix('#') {
+            if let Some((key, value)) = meta.trim_start().split_once('=') {
+                header.insert(key.to_string(), value.to_string());
+            }
+        } else if line.trim().is_empty() {
+            continue;
+        } else if let Some(columns) = &columns {
+            let row = columns
+                .iter()
+                .cloned()
+                .zip(line.split(',').map(String::from))
+                .collect();
+            positions.push(row);
+        } else {
+            columns = Some(line.split(',').map(|c| c.trim().to_string()).collect());
+        }
+    }
+
+    route_from_fields(&header, &positions)
+}
+
+/// Write the route as a GPX-like XML track, with the header as attributes on the root.
+fn route_to_xml(game_mode: &str, route: &RouteData) -> Result<String, String> {
+    let mut writer = Writer::new_with_indent(Vec::new(), b' ', 2);
+    let to_err = |e: std::io::Error| format!("Failed to write route XML: {}", e);
+
+    writer
+        .write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))
+        .map_err(to_err)?;
+
+    let mut root = BytesStart::new("route");
+    for (key, value) in HEADER_FIELDS.iter().zip(header_values(game_mode, route)) {
+        root.push_attribute((*key, value.as_str()));
+    }
+    writer.write_event(Event::Start(root)).map_err(to_err)?;
+    writer
+        .write_event(Event::Start(BytesStart::new("trk")))
+        .map_err(to_err)?;
+    writer
+        .write_event(Event::Start(BytesStart::new("trkseg")))
+        .map_err(to_err)?;
+
+    for position in &route.positions {
+        let mut point = BytesStart::new("trkpt");
+        for (key, value) in CSV_COLUMNS.iter().zip(position_values(position)) {
+            point.push_attribute((*key, value.as_str()));
+        }
+        writer.write_event(Event::Empty(point)).map_err(to_err)?;
+    }
+
+    for tag in ["trkseg", "trk", "route"] {
+        writer
+            .write_event(Event::End(BytesEnd::new(tag)))
+            .map_err(to_err)?;
+    }
+
+    String::from_utf8(writer.into_inner()).map_err(|e| e.to_string())
+}
+
+fn route_from_xml(content: &str) -> Result<(Option<String>, RouteData), String> {
+    let mut reader = Reader::from_str(content);
+    let mut header = HashMap::new();
+    let mut positions = Vec::new();
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| format!("Failed to parse route XML: {}", e))?;
+        match event {
+            Event::Start(element) | Event::Empty(element) => match element.name().as_ref() {
+                b"route" => header = xml_attributes(&element)?,
+                b"trkpt" => positions.push(xml_attributes(&element)?),
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    route_from_fields(&header, &positions)
+}
+
+fn xml_attributes(element: &BytesStart) -> Result<HashMap<String, String>, String> {
+    element
+        .attributes()
+        .map(|attribute| {
+            let attribute = attribute.map_err(|e| format!("Invalid XML attribute: {}", e))?;
+            let key = String::from_utf8_lossy(attribute.key.as_ref()).to_string();
+            let value = attribute
+                .unescape_value()
+                .map_err(|e| format!("Invalid XML attribute: {}", e))?;
+            Ok((key, value.to_string()))
+        })
+        .collect()
+}
@@ -0,0 +1,172 @@
+use super::route_stats::speed;
+use super::routes::{get_route_files, load_route, RouteData};
+use serde::Serialize;
+use tauri::command;
+
+/// Default width of a heatmap cell, in Unreal units.
+const DEFAULT_CELL_SIZE: f32 = 512.0;
+
+/// Smallest width of a heatmap cell, in Unreal units.
+const MIN_CELL_SIZE: f32 = 1.0;
+
+/// Most cells a heatmap may have, to keep responses a sane size.
+const MAX_CELLS: usize = 1_000_000;
+
+/// Route positions binned into a grid over a map's X/Y plane.
+///
+/// Matrices are indexed `[row][column]`, where row 0 starts at `min_y` and column 0 at
+/// `min_x`. Averages and ratios are 0 in cells with no samples.
+#[derive(Debug, Default, Serialize)]
+pub struct RouteHeatmap {
+    map: String,
+    cell_size: f32,
+    min_x: f32,
+    min_y: f32,
+    columns: usize,
+    rows: usize,
+    /// The routes that were binned
+    routes: Vec<String>,
+    /// Routes for the map that could not be decoded
+    skipped: Vec<String>,
+    /// The number of samples in each cell
+    density: Vec<Vec<u32>>,
+    /// The number of different routes passing through each cell
+    route_counts: Vec<Vec<u32>>,
+    average_speed: Vec<Vec<f32>>,
+    ski_ratio: Vec<Vec<f32>>,
+}
+
+/// Bin every route for a map into a heatmap grid.
+///
+/// # Arguments
+///
+/// * `map` - The map name as it appears in route file names
+/// * `cell_size` - The width of a cell in Unreal units, defaults to 512
+///
+/// # Returns
+///
+/// * `Result<RouteHeatmap, String>` - The heatmap or an error message
+#[command]
+pub fn route_heatmap(map: String, cell_size: Option<f32>) -> Result<RouteHeatmap, String> {
+    let cell_size = cell_size.unwrap_or(DEFAULT_CELL_SIZE);
+    if !cell_size.is_finite() || cell_size < MIN_CELL_SIZE {
+        return Err(format!("Cell size must be at least {}", MIN_CELL_SIZE));
+    }
+
+    let mut heatmap = RouteHeatmap {
+        map,
+        cell_size,
+        ..Default::default()
+    };
+
+    let mut decoded = Vec::new();
+    for route in get_route_files()?.routes {
+        if !route.map.eq_ignore_ascii_case(&heatmap.map) {
+            continue;
+        }
+        match load_route(&route.file_name) {
+            Ok(data) => {
+                heatmap.routes.push(route.file_name);
+                decoded.push(data);
+            }
+            Err(_) => heatmap.skipped.push(route.file_name),
+        }
+    }
+
+    let Some((min_x, min_y, max_x, max_y)) = bounds(&decoded) else {
+        return Ok(heatmap);
+    };
+    let (Some(columns), Some(rows)) = (
+        cell_index(max_x, min_x, cell_size),
+        cell_index(max_y, min_y, cell_size),
+    ) else {
+        return Err("Routes cover too large an area, use a larger cell size".into());
+    };
+    let (columns, rows) = (columns + 1, rows + 1);
+    if columns.saturating_mul(rows) > MAX_CELLS {
+        return Err(format!(
+            "Grid would have {} by {} cells, use a larger cell size",
+            columns, rows
+        ));
+    }
+
+    let mut density = vec![vec![0u32; columns]; rows];
+    let mut route_counts = vec![vec![0u32; columns]; rows];
+    let mut speed_total = vec![vec![0f32; columns]; rows];
+    let mut skiing = vec![vec![0u32; columns]; rows];
+    let mut last_route = vec![vec![None; columns]; rows];
+
+    for (route_index, route) in decoded.iter().enumerate() {
+        for position in &route.positions {
+            let (x, y, _) = position.loc;
+            if !x.is_finite() || !y.is_finite() {
+                continue;
+            }
+            let column =
+                cell_index(x, min_x, cell_size).map_or(columns - 1, |c| c.min(columns - 1));
+            let row = cell_index(y, min_y, cell_size).map_or(rows - 1, |r| r.min(rows - 1));
+
+            density[row][column] += 1;
+            speed_total[row][column] += speed(position);
+            if position.skiing {
+                skiing[row][column] += 1;
+            }
+            if last_route[row][column] != Some(route_index) {
+                last_route[row][column] = Some(route_index);
+                route_counts[row][column] += 1;
+            }
+        }
+    }
+
+    let per_sample = |totals: Vec<Vec<f32>>| -> Vec<Vec<f32>> {
+        totals
+            .into_iter()
+            .zip(&density)
+            .map(|(row, counts)| {
+                row.into_iter()
+                    .zip(counts)
+                    .map(|(total, &count)| if count > 0 { total / count as f32 } else { 0.0 })
+                    .collect()
+            })
+            .collect()
+    };
+    let skiing = skiing
+        .into_iter()
+        .map(|row| row.into_iter().map(|count| count as f32).collect())
+        .collect();
+
+    heatmap.average_speed = per_sample(speed_total);
+    heatmap.ski_ratio = per_sample(skiing);
+    heatmap.min_x = min_x;
+    heatmap.min_y = min_y;
+    heatmap.columns = columns;
+    heatmap.rows = rows;
+    heatmap.density = density;
+    heatmap.route_counts = route_counts;
+
+    Ok(heatmap)
+}
+
+/// The cell a coordinate falls in along one axis of a grid starting at `min`, if it is
+/// within `MAX_CELLS` cells of the start.
+///
+/// Worked out in f64 so that the distance between far apart coordinates cannot overflow.
+fn cell_index(value: f32, min: f32, cell_size: f32) -> Option<usize> {
+    let index = ((value as f64 - min as f64) / cell_size as f64).floor();
+    (index.is_finite() && (0.0..MAX_CELLS as f64).contains(&index)).then_some(index as usize)
+}
+
+/// The X/Y bounding box of every finite location in the routes.
+fn bounds(routes: &[RouteData]) -> Option<(f32, f32, f32, f32)> {
+    routes
+        .iter()
+        .flat_map(|route| &route.positions)
+        .map(|position| (position.loc.0, position.loc.1))
+        .filter(|(x, y)| x.is_finite() && y.is_finite())
+        .fold(None, |bounds, (x, y)| match bounds {
+            None => Some((x, y, x, y)),
+            Some((min_x, min_y, max_x, max_y)) => {
+                Some((min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y)))
+            }
+        })
+}
@@ -0,0 +1,300 @@
+use super::data::{get_app_local_data_dir, CONFIG_DIR};
+use super::route_names::parse_route_file_name;
+use super::routes::{decode_route_file, Route, RouteData};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+use tauri::AppHandle;
+
+/// File in the app local data directory that holds the route index.
+const ROUTE_INDEX_FILE: &str = "route_index.json";
+
+/// Number of routes per page when the query does not say.
+const DEFAULT_PAGE_SIZE: usize = 100;
+
+/// The in-memory copy of the route index, loaded from disk on first use.
+static ROUTE_INDEX: Lazy<Mutex<Option<RouteIndex>>> = Lazy::new(|| Mutex::new(None));
+
+/// Header metadata read from a route file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteHeader {
+    map_name: String,
+    class_abbr: String,
+    player_name: String,
+    description: String,
+    team_num: u8,
+    flag_grab_time: f32,
+    route_length: u32,
+    position_count: usize,
+    duration: f32,
+}
+
+impl From<&RouteData> for RouteHeader {
+    fn from(route: &RouteData) -> Self {
+        let duration = match (route.positions.first(), route.positions.last()) {
+            (Some(first), Some(last)) => last.time - first.time,
+            _ => 0.0,
+        };
+        RouteHeader {
+            map_name: route.map_name.clone(),
+            class_abbr: route.class_abbr.clone(),
+            player_name: route.player_name.clone(),
+            description: route.description.clone(),
+            team_num: route.team_num,
+            flag_grab_time: route.flag_grab_time,
+            route_length: route.route_length,
+            position_count: route.positions.len(),
+            duration,
+        }
+    }
+}
+
+/// A route file in the index, with the file state it was read at.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteIndexEntry {
+    pub(crate) file_name: String,
+    pub(crate) modified: u64,
+    size: u64,
+    pub(crate) route: Option<Route>,
+    header: Option<RouteHeader>,
+    error: Option<String>,
+}
+
+impl RouteIndexEntry {
+    /// The route time from the file name in seconds, if it is a number.
+    pub(crate) fn time(&self) -> Option<f32> {
+        self.route.as_ref()?.time.parse().ok()
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RouteIndex {
+    entries: HashMap<String, RouteIndexEntry>,
+}
+
+/// Filters, sort order and page for `query_routes`.
+///
+/// Text filters are case-insensitive and match anywhere in the field.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct RouteQuery {
+    map: Option<String>,
+    class: Option<String>,
+    side: Option<String>,
+    game_mode: Option<String>,
+    username: Option<String>,
+    /// `time` (default), `flag_grab_time`, `route_length` or `modified`
+    sort_by: Option<String>,
+    descending: bool,
+    page: usize,
+    page_size: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RouteQueryResult {
+    entries: Vec<RouteIndexEntry>,
+    total: usize,
+    page: usize,
+    page_size: usize,
+}
+
+/// Search the route library.
+///
+/// Brings the index up to date with the routes directory first, only decoding files
+/// that are new or whose modification time or size changed. The refresh runs off the
+/// main thread since it may decode the whole routes directory.
+///
+/// # Arguments
+///
+/// * `handle` - The AppHandle object
+/// * `query` - The filters, sort order and page to return
+///
+/// # Returns
+///
+/// * `Result<RouteQueryResult, String>` - The matching page of routes or an error message
+#[tauri::command]
+pub async fn query_routes(
+    handle: AppHandle,
+    query: RouteQuery,
+) -> Result<RouteQueryResult, String> {
+    tokio::task::spawn_blocking(move || run_query(&handle, query))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+fn run_query(handle: &AppHandle, query: RouteQuery) -> Result<RouteQueryResult, String> {
+    let entries = refresh_route_index(handle)?;
+
+    let mut matches: Vec<RouteIndexEntry> = entries
+        .into_iter()
+        .filter(|entry| matches_query(entry, &query))
+        .collect();
+
+    let sort_by = query.sort_by.as_deref().unwrap_or("time");
+    matches.sort_by(|a, b| {
+        let ordering = match sort_by {
+            "flag_grab_time" => compare_optional(
+                a.header.as_ref().map(|h| h.flag_grab_time as f64),
+                b.header.as_ref().map(|h| h.flag_grab_time as f64),
+            ),
+            "route_length" => compare_optional(
+                a.header.as_ref().map(|h| h.route_length as f64),
+                b.header.as_ref().map(|h| h.route_length as f64),
+            ),
+            "modified" => a.modified.cmp(&b.modified),
+            _ => compare_optional(a.time().map(f64::from), b.time().map(f64::from)),
+        };
+        let ordering = if query.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        };
+        ordering.then_with(|| a.file_name.cmp(&b.file_name))
+    });
+
+    let total = matches.len();
+    let page_size = query.page_size.unwrap_or(DEFAULT_PAGE_SIZE).max(1);
+    let entries = matches
+        .into_iter()
+        .skip(query.page.saturating_mul(page_size))
+        .take(page_size)
+        .collect();
+
+    Ok(RouteQueryResult {
+        entries,
+        total,
+        page: query.page,
+        page_size,
+    })
+}
+
+/// Bring the route index up to date and return a snapshot of its entries.
+pub(crate) fn refresh_route_index(handle: &AppHandle) -> Result<Vec<RouteIndexEntry>, String> {
+    let routes_path = CONFIG_DIR.join("routes");
+    let index_path = get_app_local_data_dir(handle).join(ROUTE_INDEX_FILE);
+
+    let mut guard = ROUTE_INDEX
+        .lock()
+        .map_err(|_| "Route index lock poisoned".to_string())?;
+    let index = guard.get_or_insert_with(|| load_route_index(&index_path));
+
+    let dir_entries = fs::read_dir(&routes_path)
+        .map_err(|e| format!("Failed to read routes directory: {}", e))?;
+
+    let mut changed = false;
+    let mut seen = HashSet::new();
+    for dir_entry in dir_entries.flatten() {
+        let path = dir_entry.path();
+        if !path.is_file() || path.extension().is_none_or(|ext| ext != "route") {
+            continue;
+        }
+        let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        let Ok(metadata) = dir_entry.metadata() else {
+            continue;
+        };
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |duration| duration.as_millis() as u64);
+
+        seen.insert(file_name.to_string());
+        let up_to_date = index
+            .entries
+            .get(file_name)
+            .is_some_and(|entry| entry.modified == modified && entry.size == metadata.len());
+        if !up_to_date {
+            let entry = index_route_file(&path, file_name, modified, metadata.len());
+            index.entries.insert(file_name.to_string(), entry);
+            changed = true;
+        }
+    }
+
+    // Drop routes that no longer exist
+    let before = index.entries.len();
+    index
+        .entries
+        .retain(|file_name, _| seen.contains(file_name));
+    changed |= index.entries.len() != before;
+
+    if changed {
+        save_route_index(&index_path, index)?;
+    }
+
+    Ok(index.entries.values().cloned().collect())
+}
+
+fn index_route_file(path: &Path, file_name: &str, modified: u64, size: u64) -> RouteIndexEntry {
+    let route = parse_route_file_name(file_name);
+    let decoded = path
+        .to_str()
+        .ok_or_else(|| "Failed to convert path to string".to_string())
+        .and_then(|path| decode_route_file(path).map_err(|e| e.to_string()));
+
+    let error = match (&route, &decoded) {
+        (Err(e), _) => Some(e.to_string()),
+        (_, Err(e)) => Some(e.clone()),
+        _ => None,
+    };
+
+    RouteIndexEntry {
+        file_name: file_name.to_string(),
+        modified,
+        size,
+        route: route.ok(),
+        header: decoded.ok().as_ref().map(RouteHeader::from),
+        error,
+    }
+}
+
+/// Load the saved index, starting over if it is missing or unreadable.
+fn load_route_index(index_path: &Path) -> RouteIndex {
+    fs::read_to_string(index_path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_route_index(index_path: &Path, index: &RouteIndex) -> Result<(), String> {
+    if let Some(parent) = index_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+    }
+    let content = serde_json::to_string(index).map_err(|e| e.to_string())?;
+    fs::write(index_path, content).map_err(|e| format!("Failed to save route index: {}", e))
+}
+
+fn matches_query(entry: &RouteIndexEntry, query: &RouteQuery) -> bool {
+    let Some(route) = &entry.route else {
+        return false;
+    };
+    let contains = |value: &str, filter: &Option<String>| {
+        filter
+            .as_ref()
+            .is_none_or(|filter| value.to_lowercase().contains(&filter.to_lowercase()))
+    };
+
+    contains(&route.map, &query.map)
+        && contains(&route.class, &query.class)
+        && contains(&route.side, &query.side)
+        && contains(&route.game_mode, &query.game_mode)
+        && contains(&route.username, &query.username)
+}
+
+/// Order missing values after present ones. NaN counts as missing, so the order is total.
+fn compare_optional(a: Option<f64>, b: Option<f64>) -> Ordering {
+    let is_number = |value: &f64| !value.is_nan();
+    match (a.filter(is_number), b.filter(is_number)) {
+        (Some(a), Some(b)) => a.total_cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
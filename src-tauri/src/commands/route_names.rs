@@ -0,0 +1,166 @@
+use super::routes::{Route, RouteData};
+use serde::Serialize;
+use std::fmt;
+
+/// Game mode to use when a route's file name does not say which mode it was recorded in.
+pub(crate) const DEFAULT_GAME_MODE: &str = "CTF";
+
+/// Characters that cannot appear in a Windows file name.
+const INVALID_FILE_NAME_CHARS: [char; 9] = ['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+
+/// Why a route file name could not be parsed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", content = "detail")]
+pub enum RouteNameError {
+    /// The file name does not end in `.route`
+    MissingExtension,
+    /// There is no `-` separating the game mode from the rest of the name
+    MissingGameMode,
+    /// A required `_` separated field is missing or empty
+    MissingField(&'static str),
+    /// The side is not `DS` or `BE`
+    InvalidSide(String),
+    /// The route name opens a bracket that is never closed
+    UnclosedRouteName,
+}
+
+impl fmt::Display for RouteNameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RouteNameError::MissingExtension => write!(f, "File name does not end in .route"),
+            RouteNameError::MissingGameMode => write!(f, "File name has no game mode prefix"),
+            RouteNameError::MissingField(field) => write!(f, "File name is missing the {}", field),
+            RouteNameError::InvalidSide(side) => {
+                write!(f, "Side must be DS or BE, found '{}'", side)
+            }
+            RouteNameError::UnclosedRouteName => {
+                write!(f, "Route name bracket is never closed")
+            }
+        }
+    }
+}
+
+/// Parse a route file name of the form `<mode>-<map>_<side>_<class>_<user>_(<name>)_<time>.route`.
+///
+/// Older routes re-encoded by the website have no brackets around the route name, in which
+/// case the username ends at the first `_` and the route name runs up to the time.
+///
+/// # Arguments
+///
+/// * `file_name` - The route file name
+///
+/// # Returns
+///
+/// * `Result<Route, RouteNameError>` - The parsed route or why it could not be parsed
+pub(crate) fn parse_route_file_name(file_name: &str) -> Result<Route, RouteNameError> {
+    let stem = file_name
+        .strip_suffix(".route")
+        .ok_or(RouteNameError::MissingExtension)?;
+
+    let (game_mode, rest) = stem
+        .split_once('-')
+        .filter(|(mode, _)| !mode.is_empty())
+        .ok_or(RouteNameError::MissingGameMode)?;
+
+    let mut fields = rest.splitn(4, '_');
+    let mut next_field = |name: &'static str| {
+        fields
+            .next()
+            .filter(|field| !field.is_empty())
+            .ok_or(RouteNameError::MissingField(name))
+    };
+    let map = next_field("map")?;
+    let side = next_field("side")?;
+    let class = next_field("class")?;
+    let remainder = next_field("username")?;
+
+    if side != "DS" && side != "BE" {
+        return Err(RouteNameError::InvalidSide(side.to_string()));
+    }
+
+    // The time always follows the last '_'
+    let (head, time) = remainder
+        .rsplit_once('_')
+        .filter(|(_, time)| !time.is_empty())
+        .ok_or(RouteNameError::MissingField("time"))?;
+
+    let (username, route_name) = match head.split_once("_(") {
+        Some((username, bracketed)) => {
+            let route_name = bracketed
+                .strip_suffix(')')
+                .ok_or(RouteNameError::UnclosedRouteName)?;
+            (username, route_name)
+        }
+        None => head
+            .split_once('_')
+            .ok_or(RouteNameError::MissingField("route name"))?,
+    };
+    if username.is_empty() {
+        return Err(RouteNameError::MissingField("username"));
+    }
+
+    Ok(Route {
+        game_mode: game_mode.to_string(),
+        map: map.to_string(),
+        side: side.to_string(),
+        class: class.to_string(),
+        username: username.to_string(),
+        route_name: route_name.to_string(),
+        time: time.to_string(),
+        file_name: file_name.to_string(),
+    })
+}
+
+/// Build the canonical file name for a route, always bracketing the route name.
+///
+/// Characters that would break the grammar or are not allowed in file names are dropped
+/// from each field, so the result always parses back to the same fields.
+///
+/// # Arguments
+///
+/// * `route` - The route metadata, `file_name` is ignored
+pub(crate) fn build_route_file_name(route: &Route) -> String {
+    format!(
+        "{}-{}_{}_{}_{}_({})_{}.route",
+        clean_field(&route.game_mode, &['-', '_']),
+        clean_field(&route.map, &['_']),
+        clean_field(&route.side, &['_']),
+        clean_field(&route.class, &['_']),
+        clean_field(&route.username, &['_', '(']),
+        clean_field(&route.route_name, &[]),
+        clean_field(&route.time, &['_']),
+    )
+}
+
+/// Build the canonical file name for a decoded route from its header.
+///
+/// # Arguments
+///
+/// * `game_mode` - The game mode the route was recorded in (e.g. `CTF`)
+/// * `route` - The decoded route
+pub(crate) fn route_file_name(game_mode: &str, route: &RouteData) -> String {
+    let time = match (route.positions.first(), route.positions.last()) {
+        (Some(first), Some(last)) => last.time - first.time,
+        _ => 0.0,
+    };
+
+    build_route_file_name(&Route {
+        game_mode: game_mode.to_string(),
+        map: route.map_name.clone(),
+        side: if route.team_num == 0 { "BE" } else { "DS" }.to_string(),
+        class: route.class_abbr.clone(),
+        username: route.player_name.clone(),
+        route_name: route.description.clone(),
+        time: format!("{:.2}", time),
+        file_name: String::new(),
+    })
+}
+
+fn clean_field(value: &str, separators: &[char]) -> String {
+    value
+        .chars()
+        .filter(|c| {
+            !c.is_control() && !INVALID_FILE_NAME_CHARS.contains(c) && !separators.contains(c)
+        })
+        .collect()
+}
@@ -0,0 +1,158 @@
+use super::routes::{load_route, Position};
+use serde::Serialize;
+use tauri::command;
+
+/// Most frames a single request may return per route, to keep responses a sane size.
+const MAX_FRAMES: usize = 100_000;
+
+/// Rotator units in a full turn.
+const FULL_TURN: i64 = 65536;
+
+/// A route sampled on the shared timeline.
+#[derive(Debug, Serialize)]
+pub struct SampledRoute {
+    file_name: String,
+    /// The state at each timeline time, `None` before the route starts or after it ends
+    frames: Vec<Option<Position>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RouteSamples {
+    /// The timeline times, in seconds from the start of each route
+    times: Vec<f32>,
+    routes: Vec<SampledRoute>,
+}
+
+/// Sample several routes at the same times for synchronized playback.
+///
+/// Each route's clock starts at its first position, so every route starts at zero on the
+/// shared timeline. `loc`, `vel`, `energy`, `health` and `eta` are interpolated linearly,
+/// `pitch` and `yaw` the short way around, and `skiing`, `jetting` and `phys` hold the
+/// state of the last recorded sample until the next one changes it.
+///
+/// # Arguments
+///
+/// * `files` - The route file names in the routes directory
+/// * `t_start` - The first time to sample, in seconds
+/// * `t_end` - The last time to sample, in seconds
+/// * `hz` - How many samples to take per second
+///
+/// # Returns
+///
+/// * `Result<RouteSamples, String>` - The timeline and a frame per time for each route or
+///   an error message
+#[command]
+pub fn sample_routes(
+    files: Vec<String>,
+    t_start: f32,
+    t_end: f32,
+    hz: f32,
+) -> Result<RouteSamples, String> {
+    if !hz.is_finite() || hz <= 0.0 {
+        return Err("Sample rate must be greater than zero".into());
+    }
+    if !t_start.is_finite() || !t_end.is_finite() || t_end < t_start {
+        return Err("End time must not be before start time".into());
+    }
+
+    // Counted in f64 so a huge range or rate cannot overflow before it is checked
+    let frame_count = ((t_end as f64 - t_start as f64) * hz as f64).floor() + 1.0;
+    if !frame_count.is_finite() || frame_count > MAX_FRAMES as f64 {
+        return Err(format!(
+            "Too many frames ({}), lower the sample rate or the time range",
+            frame_count
+        ));
+    }
+    let frame_count = frame_count as usize;
+    let times: Vec<f32> = (0..frame_count).map(|i| t_start + i as f32 / hz).collect();
+
+    let mut routes = Vec::with_capacity(files.len());
+    for file in files {
+        let route = load_route(&file)?;
+        routes.push(SampledRoute {
+            frames: sample_positions(&route.positions, &times),
+            file_name: file,
+        });
+    }
+
+    Ok(RouteSamples { times, routes })
+}
+
+/// Sample positions at increasing times measured from the first position.
+pub(crate) fn sample_positions(positions: &[Position], times: &[f32]) -> Vec<Option<Position>> {
+    let Some(first) = positions.first() else {
+        return vec![None; times.len()];
+    };
+    let start = first.time;
+
+    let mut segment = 0;
+    times
+        .iter()
+        .map(|&t| {
+            let time = start + t;
+            // Times only increase, so the segment only ever moves forward
+            while segment + 1 < positions.len() && positions[segment + 1].time < time {
+                segment += 1;
+            }
+
+            let a = &positions[segment];
+            let Some(b) = positions.get(segment + 1) else {
+                // A single position route only exists at its own time
+                return (time == a.time).then(|| Position {
+                    time: t,
+                    ..a.clone()
+                });
+            };
+            if time < a.time || time > b.time {
+                return None;
+            }
+
+            let span = b.time - a.time;
+            let fraction = if span > 0.0 {
+                (time - a.time) / span
+            } else {
+                0.0
+            };
+            let mut frame = interpolate_position(a, b, fraction);
+            frame.time = t;
+            Some(frame)
+        })
+        .collect()
+}
+
+/// The state between two recorded positions.
+fn interpolate_position(a: &Position, b: &Position, fraction: f32) -> Position {
+    let lerp = |x: f32, y: f32| x + (y - x) * fraction;
+    Position {
+        time: lerp(a.time, b.time),
+        loc: (
+            lerp(a.loc.0, b.loc.0),
+            lerp(a.loc.1, b.loc.1),
+            lerp(a.loc.2, b.loc.2),
+        ),
+        vel: (
+            lerp(a.vel.0, b.vel.0),
+            lerp(a.vel.1, b.vel.1),
+            lerp(a.vel.2, b.vel.2),
+        ),
+        pitch: lerp_angle(a.pitch, b.pitch, fraction),
+        yaw: lerp_angle(a.yaw, b.yaw, fraction),
+        phys: a.phys,
+        skiing: a.skiing,
+        jetting: a.jetting,
+        health: lerp(a.health as f32, b.health as f32).round() as u8,
+        energy: lerp(a.energy, b.energy),
+        eta: lerp(a.eta as f32, b.eta as f32).round() as i32,
+        padding: a.padding,
+        raw_flags: a.raw_flags,
+    }
+}
+
+/// Interpolate between two rotator angles the short way around the circle.
+fn lerp_angle(a: i32, b: i32, fraction: f32) -> i32 {
+    let mut delta = (b as i64 - a as i64).rem_euclid(FULL_TURN);
+    if delta > FULL_TURN / 2 {
+        delta -= FULL_TURN;
+    }
+    (a as i64 + (delta as f64 * fraction as f64).round() as i64) as i32
+}
@@ -0,0 +1,316 @@
+use super::data::{get_app_local_data_dir, CONFIG_DIR};
+use super::route_bundle::content_hash;
+use super::route_simplify::simplify_route;
+use super::route_stats::speed;
+use super::routes::{read_route, RouteData};
+use log::warn;
+use serde::Serialize;
+use std::fmt::Write;
+use std::fs;
+use tauri::AppHandle;
+
+/// Folder in the app local data directory that rendered previews are cached in.
+const PREVIEW_CACHE_DIR: &str = "route_previews";
+
+/// Width and height of a preview when the caller does not say, in pixels.
+const DEFAULT_PREVIEW_SIZE: u32 = 256;
+
+/// The smallest and largest previews that can be rendered, in pixels.
+const PREVIEW_SIZE_RANGE: (u32, u32) = (16, 2048);
+
+/// Most positions drawn in a preview, the path is simplified down to this.
+const MAX_PREVIEW_POINTS: usize = 1000;
+
+/// Colours of the halo drawn under skiing and jetting segments.
+const SKIING_COLOUR: [u8; 3] = [0, 220, 255];
+const JETTING_COLOUR: [u8; 3] = [255, 140, 0];
+
+/// Opacity of the skiing and jetting halos.
+const HALO_OPACITY: f32 = 0.45;
+
+/// A rendered route preview.
+#[derive(Debug, Serialize)]
+#[serde(tag = "format", content = "data", rename_all = "lowercase")]
+pub enum RoutePreview {
+    Svg(String),
+    Png(Vec<u8>),
+}
+
+/// A straight piece of the path in pixel coordinates.
+struct Segment {
+    from: (f32, f32),
+    to: (f32, f32),
+    colour: [u8; 3],
+    skiing: bool,
+    jetting: bool,
+}
+
+/// A set of strokes that are drawn and then blended onto the preview together.
+struct Layer {
+    /// A fixed colour, or `None` to colour each segment by speed
+    colour: Option<[u8; 3]>,
+    width: f32,
+    opacity: f32,
+    include: fn(&Segment) -> bool,
+}
+
+/// The layers of a preview from the bottom up, with line widths scaled to its size.
+fn preview_layers(size: u32) -> [Layer; 3] {
+    let path_width = (size as f32 / 128.0).max(1.0);
+    [
+        Layer {
+            colour: Some(SKIING_COLOUR),
+            width: path_width * 4.0,
+            opacity: HALO_OPACITY,
+            include: |s| s.skiing,
+        },
+        Layer {
+            colour: Some(JETTING_COLOUR),
+            width: path_width * 2.5,
+            opacity: HALO_OPACITY,
+            include: |s| s.jetting,
+        },
+        Layer {
+            colour: None,
+            width: path_width,
+            opacity: 1.0,
+            include: |_| true,
+        },
+    ]
+}
+
+/// Render a top-down preview of a route's path.
+///
+/// The path is coloured from blue when slow to red at the route's top speed, with a cyan
+/// halo under skiing segments and an orange one under jetting segments. Previews are cached
+/// by the hash of the route file, so renaming a route keeps its preview.
+///
+/// # Arguments
+///
+/// * `handle` - The AppHandle object
+/// * `file` - The route file name in the routes directory
+/// * `format` - `svg` or `png`
+/// * `size` - The width and height in pixels, defaults to 256
+///
+/// # Returns
+///
+/// * `Result<RoutePreview, String>` - The SVG document or PNG bytes or an error message
+#[tauri::command]
+pub fn render_route_preview(
+    handle: AppHandle,
+    file: String,
+    format: String,
+    size: Option<u32>,
+) -> Result<RoutePreview, String> {
+    let size = size
+        .unwrap_or(DEFAULT_PREVIEW_SIZE)
+        .clamp(PREVIEW_SIZE_RANGE.0, PREVIEW_SIZE_RANGE.1);
+    let format = format.to_lowercase();
+    if format != "svg" && format != "png" {
+        return Err(format!("Unsupported preview format: {}", format));
+    }
+
+    let content = fs::read(CONFIG_DIR.join("routes").join(&file))
+        .map_err(|e| format!("Failed to read route {}: {}", file, e))?;
+
+    let cache_dir = get_app_local_data_dir(&handle).join(PREVIEW_CACHE_DIR);
+    let cache_path = cache_dir.join(format!("{}_{}.{}", content_hash(&content), size, format));
+    if let Ok(cached) = fs::read(&cache_path) {
+        return Ok(match format.as_str() {
+            "svg" => RoutePreview::Svg(String::from_utf8_lossy(&cached).to_string()),
+            _ => RoutePreview::Png(cached),
+        });
+    }
+
+    let route = read_route(&mut content.as_slice())
+        .map_err(|e| format!("Failed to decode route {}: {}", file, e))?;
+    let segments = route_segments(&route, size);
+    let preview = match format.as_str() {
+        "svg" => RoutePreview::Svg(render_svg(&segments, size)),
+        _ => RoutePreview::Png(render_png(&segments, size)?),
+    };
+
+    // A preview that cannot be cached is still worth returning
+    let bytes = match &preview {
+        RoutePreview::Svg(svg) => svg.as_bytes(),
+        RoutePreview::Png(png) => png.as_slice(),
+    };
+    if let Err(e) = fs::create_dir_all(&cache_dir).and_then(|_| fs::write(&cache_path, bytes)) {
+        warn!("Failed to cache preview for {}: {}", file, e);
+    }
+
+    Ok(preview)
+}
+
+/// Fit the simplified path into the preview and colour each segment.
+fn route_segments(route: &RouteData, size: u32) -> Vec<Segment> {
+    let positions: Vec<_> = simplify_route(route, None, Some(MAX_PREVIEW_POINTS))
+        .into_iter()
+        .map(|index| &route.positions[index])
+        .filter(|p| p.loc.0.is_finite() && p.loc.1.is_finite())
+        .collect();
+    if positions.len() < 2 {
+        return Vec::new();
+    }
+
+    let (mut min_x, mut min_y, mut max_x, mut max_y) = (f32::MAX, f32::MAX, f32::MIN, f32::MIN);
+    for p in &positions {
+        min_x = min_x.min(p.loc.0);
+        min_y = min_y.min(p.loc.1);
+        max_x = max_x.max(p.loc.0);
+        max_y = max_y.max(p.loc.1);
+    }
+
+    // Keep the aspect ratio and leave room for the halos at the edges
+    let padding = preview_layers(size)[0].width;
+    let drawable = size as f32 - 2.0 * padding;
+    let scale = drawable / (max_x - min_x).max(max_y - min_y).max(1.0);
+    let offset_x = padding + (drawable - (max_x - min_x) * scale) / 2.0;
+    let offset_y = padding + (drawable - (max_y - min_y) * scale) / 2.0;
+    // Y points up on the map but down in the image
+    let to_pixel = |loc: (f32, f32, f32)| {
+        (
+            offset_x + (loc.0 - min_x) * scale,
+            size as f32 - (offset_y + (loc.1 - min_y) * scale),
+        )
+    };
+
+    let top_speed = positions
+        .iter()
+        .map(|p| speed(p))
+        .filter(|s| s.is_finite())
+        .fold(0.0, f32::max);
+
+    positions
+        .windows(2)
+        .map(|pair| Segment {
+            from: to_pixel(pair[0].loc),
+            to: to_pixel(pair[1].loc),
+            colour: speed_colour(if top_speed > 0.0 {
+                speed(pair[0]) / top_speed
+            } else {
+                0.0
+            }),
+            skiing: pair[0].skiing,
+            jetting: pair[0].jetting,
+        })
+        .collect()
+}
+
+/// Blue at 0 through green to red at 1.
+fn speed_colour(fraction: f32) -> [u8; 3] {
+    let hue = 240.0 * (1.0 - fraction.clamp(0.0, 1.0));
+    let x = 1.0 - ((hue / 60.0) % 2.0 - 1.0).abs();
+    let (r, g, b) = match (hue / 60.0) as u32 {
+        0 => (1.0, x, 0.0),
+        1 => (x, 1.0, 0.0),
+        2 => (0.0, 1.0, x),
+        _ => (0.0, x, 1.0),
+    };
+    [(r * 255.0) as u8, (g * 255.0) as u8, (b * 255.0) as u8]
+}
+
+fn render_svg(segments: &[Segment], size: u32) -> String {
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{0}" viewBox="0 0 {0} {0}">"#,
+        size
+    );
+
+    for layer in preview_layers(size) {
+        let _ = write!(
+            svg,
+            r#"<g opacity="{}" stroke-width="{:.1}" stroke-linecap="round">"#,
+            layer.opacity, layer.width
+        );
+        for segment in segments.iter().filter(|s| (layer.include)(s)) {
+            let [r, g, b] = layer.colour.unwrap_or(segment.colour);
+            let _ = write!(
+                svg,
+                r#"<line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="rgb({},{},{})"/>"#,
+                segment.from.0, segment.from.1, segment.to.0, segment.to.1, r, g, b
+            );
+        }
+        svg.push_str("</g>");
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+fn render_png(segments: &[Segment], size: u32) -> Result<Vec<u8>, String> {
+    let mut canvas = vec![0u8; (size * size * 4) as usize];
+
+    for layer in preview_layers(size) {
+        // Cover the whole layer before blending so overlapping strokes do not darken
+        let mut covered: Vec<Option<[u8; 3]>> = vec![None; (size * size) as usize];
+        for segment in segments.iter().filter(|s| (layer.include)(s)) {
+            let colour = layer.colour.unwrap_or(segment.colour);
+            stroke(&mut covered, size, segment, layer.width / 2.0, colour);
+        }
+        for (pixel, colour) in canvas.chunks_exact_mut(4).zip(&covered) {
+            if let Some(colour) = colour {
+                blend(pixel, *colour, layer.opacity);
+            }
+        }
+    }
+
+    let mut png = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png, size, size);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder
+        .write_header()
+        .map_err(|e| format!("Failed to encode preview: {}", e))?;
+    writer
+        .write_image_data(&canvas)
+        .map_err(|e| format!("Failed to encode preview: {}", e))?;
+    writer
+        .finish()
+        .map_err(|e| format!("Failed to encode preview: {}", e))?;
+
+    Ok(png)
+}
+
+/// Cover every pixel within `radius` of the segment.
+fn stroke(
+    layer: &mut [Option<[u8; 3]>],
+    size: u32,
+    segment: &Segment,
+    radius: f32,
+    colour: [u8; 3],
+) {
+    let (a, b) = (segment.from, segment.to);
+    let x_range = (a.0.min(b.0) - radius).floor().max(0.0) as u32
+        ..=((a.0.max(b.0) + radius).ceil().max(0.0) as u32).min(size - 1);
+    let y_range = (a.1.min(b.1) - radius).floor().max(0.0) as u32
+        ..=((a.1.max(b.1) + radius).ceil().max(0.0) as u32).min(size - 1);
+
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let length_sq = dx * dx + dy * dy;
+    for y in y_range {
+        for x in x_range.clone() {
+            let (px, py) = (x as f32 + 0.5, y as f32 + 0.5);
+            let t = if length_sq > 0.0 {
+                (((px - a.0) * dx + (py - a.1) * dy) / length_sq).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let (cx, cy) = (a.0 + dx * t - px, a.1 + dy * t - py);
+            if cx * cx + cy * cy <= radius * radius {
+                layer[(y * size + x) as usize] = Some(colour);
+            }
+        }
+    }
+}
+
+/// Draw a colour over an RGBA pixel with the given opacity.
+fn blend(pixel: &mut [u8], colour: [u8; 3], opacity: f32) {
+    let dst_alpha = pixel[3] as f32 / 255.0;
+    let alpha = opacity + dst_alpha * (1.0 - opacity);
+    for channel in 0..3 {
+        let src = colour[channel] as f32 * opacity;
+        let dst = pixel[channel] as f32 * dst_alpha * (1.0 - opacity);
+        pixel[channel] = ((src + dst) / alpha).round() as u8;
+    }
+    pixel[3] = (alpha * 255.0).round() as u8;
+}
@@ -0,0 +1,113 @@
+use super::route_compare::project;
+use super::route_stats::distance;
+use super::routes::{Position, RouteData};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+/// A stretch of the path between two kept samples and the sample furthest from it.
+struct Split {
+    deviation: f32,
+    start: usize,
+    end: usize,
+    furthest: usize,
+}
+
+impl PartialEq for Split {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Split {}
+
+impl PartialOrd for Split {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Split {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.deviation.total_cmp(&other.deviation)
+    }
+}
+
+/// Reduce a route's path with Ramer–Douglas–Peucker in 3D.
+///
+/// The stretch with the sample furthest from it is always split first, so sharp direction
+/// changes are kept before gentle ones. Splitting stops once no sample is further than
+/// `tolerance` from the reduced path, or once `max_points` samples are kept. The first
+/// and last samples and the flag grab are always kept, even if that goes over the budget.
+///
+/// # Arguments
+///
+/// * `route` - The decoded route
+/// * `tolerance` - The furthest a dropped sample may be from the reduced path, in Unreal units
+/// * `max_points` - The most samples to keep
+///
+/// # Returns
+///
+/// * `Vec<usize>` - The indices of the kept samples, in order
+pub(crate) fn simplify_route(
+    route: &RouteData,
+    tolerance: Option<f32>,
+    max_points: Option<usize>,
+) -> Vec<usize> {
+    let positions = &route.positions;
+    if positions.len() <= 2 {
+        return (0..positions.len()).collect();
+    }
+
+    let grab = route.flag_grab_time;
+    let grab_index = positions.iter().position(|p| p.time >= grab);
+    let mut kept: Vec<usize> = [Some(0), grab_index, Some(positions.len() - 1)]
+        .into_iter()
+        .flatten()
+        .collect();
+    kept.dedup();
+
+    let tolerance = tolerance.unwrap_or(0.0);
+    let max_points = max_points.unwrap_or(usize::MAX);
+
+    let mut splits: BinaryHeap<Split> = kept
+        .windows(2)
+        .filter_map(|pair| furthest_sample(positions, pair[0], pair[1]))
+        .collect();
+    while kept.len() < max_points {
+        let Some(split) = splits.pop() else {
+            break;
+        };
+        if split.deviation <= tolerance {
+            break;
+        }
+        kept.push(split.furthest);
+        splits.extend(furthest_sample(positions, split.start, split.furthest));
+        splits.extend(furthest_sample(positions, split.furthest, split.end));
+    }
+
+    kept.sort_unstable();
+    kept
+}
+
+/// The sample between `start` and `end` furthest from the straight line joining them.
+fn furthest_sample(positions: &[Position], start: usize, end: usize) -> Option<Split> {
+    let (a, b) = (positions[start].loc, positions[end].loc);
+    (start + 1..end)
+        .map(|index| {
+            let loc = positions[index].loc;
+            let fraction = project(a, b, loc);
+            let closest = (
+                a.0 + (b.0 - a.0) * fraction,
+                a.1 + (b.1 - a.1) * fraction,
+                a.2 + (b.2 - a.2) * fraction,
+            );
+            (index, distance(closest, loc))
+        })
+        .max_by(|x, y| x.1.total_cmp(&y.1))
+        .map(|(furthest, deviation)| Split {
+            deviation,
+            start,
+            end,
+            furthest,
+        })
+}
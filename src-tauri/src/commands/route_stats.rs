@@ -0,0 +1,198 @@
+use super::map_registry::{find_map_info, FlagStands};
+use super::routes::{load_route, Position, RouteData};
+use serde::Serialize;
+use tauri::{command, AppHandle};
+
+/// Summary numbers for a stretch of a route.
+///
+/// Distances are in Unreal units and speeds in Unreal units per second.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct SegmentStats {
+    duration: f32,
+    path_length: f32,
+    average_speed: f32,
+    peak_speed: f32,
+    time_skiing: f32,
+    time_jetting: f32,
+    ski_ratio: f32,
+    jet_ratio: f32,
+    energy_spent: f32,
+    energy_regenerated: f32,
+    health_lost: u32,
+    height_gain: f32,
+    height_loss: f32,
+}
+
+/// A part of the route split at the flag grab.
+#[derive(Debug, Serialize)]
+pub struct RouteSplit {
+    label: String,
+    start_time: f32,
+    end_time: f32,
+    stats: SegmentStats,
+}
+
+/// Distances to the flag stands, in Unreal units.
+#[derive(Debug, Serialize)]
+pub struct FlagStats {
+    /// From the first position to the enemy flag stand
+    start_to_enemy_flag: f32,
+    /// The closest the route comes to the enemy flag stand
+    closest_to_enemy_flag: f32,
+    /// When the route is closest to the enemy flag stand
+    closest_time: f32,
+    /// From the last position to the route's own flag stand
+    end_to_home_flag: f32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RouteStats {
+    total: SegmentStats,
+    splits: Vec<RouteSplit>,
+    /// Only present when the map registry knows where the flag stands are
+    flag: Option<FlagStats>,
+}
+
+/// Compute the statistics for a route file.
+///
+/// # Arguments
+///
+/// * `handle` - The AppHandle object
+/// * `file` - The route file name in the routes directory
+///
+/// # Returns
+///
+/// * `Result<RouteStats, String>` - The route statistics or an error message
+#[command]
+pub fn route_stats(handle: AppHandle, file: String) -> Result<RouteStats, String> {
+    let route = load_route(&file)?;
+    let mut stats = compute_route_stats(&route);
+    stats.flag = find_map_info(&handle, &route.map_name)
+        .and_then(|map| map.flag_stands)
+        .and_then(|stands| compute_flag_stats(&route, &stands));
+    Ok(stats)
+}
+
+/// Compute the statistics for a decoded route, split at the flag grab time.
+pub(crate) fn compute_route_stats(route: &RouteData) -> RouteStats {
+    let positions = &route.positions;
+    let total = compute_segment_stats(positions);
+
+    // Only split if the flag was grabbed somewhere inside the recorded samples
+    let grab = route.flag_grab_time;
+    let grab_index = positions.iter().position(|p| p.time >= grab);
+    let splits = match grab_index {
+        Some(index) if index > 0 && index < positions.len() - 1 => vec![
+            make_split("to_flag", &positions[..=index]),
+            make_split("from_flag", &positions[index..]),
+        ],
+        _ => Vec::new(),
+    };
+
+    RouteStats {
+        total,
+        splits,
+        flag: None,
+    }
+}
+
+/// Measure a route against the flag stands, the enemy one being the one it runs to.
+pub(crate) fn compute_flag_stats(route: &RouteData, stands: &FlagStands) -> Option<FlagStats> {
+    // Team 0 is Blood Eagle, so its routes grab the Diamond Sword flag
+    let (home, enemy) = if route.team_num == 0 {
+        (stands.be, stands.ds)
+    } else {
+        (stands.ds, stands.be)
+    };
+
+    let first = route.positions.first()?;
+    let last = route.positions.last()?;
+    let closest = route
+        .positions
+        .iter()
+        .map(|p| (p.time, distance(p.loc, enemy)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))?;
+
+    Some(FlagStats {
+        start_to_enemy_flag: distance(first.loc, enemy),
+        closest_to_enemy_flag: closest.1,
+        closest_time: closest.0 - first.time,
+        end_to_home_flag: distance(last.loc, home),
+    })
+}
+
+fn make_split(label: &str, positions: &[Position]) -> RouteSplit {
+    RouteSplit {
+        label: label.to_string(),
+        start_time: positions.first().map_or(0.0, |p| p.time),
+        end_time: positions.last().map_or(0.0, |p| p.time),
+        stats: compute_segment_stats(positions),
+    }
+}
+
+/// Compute the statistics over a run of consecutive positions.
+///
+/// Each interval between two samples is attributed to the state of the first sample.
+pub(crate) fn compute_segment_stats(positions: &[Position]) -> SegmentStats {
+    let mut stats = SegmentStats::default();
+    if positions.is_empty() {
+        return stats;
+    }
+
+    let mut weighted_speed = 0.0;
+    stats.peak_speed = positions.iter().map(speed).fold(0.0, f32::max);
+
+    for pair in positions.windows(2) {
+        let (a, b) = (&pair[0], &pair[1]);
+        let dt = (b.time - a.time).max(0.0);
+
+        stats.duration += dt;
+        stats.path_length += distance(a.loc, b.loc);
+        weighted_speed += speed(a) * dt;
+
+        if a.skiing {
+            stats.time_skiing += dt;
+        }
+        if a.jetting {
+            stats.time_jetting += dt;
+        }
+
+        let energy_delta = b.energy - a.energy;
+        if energy_delta < 0.0 {
+            stats.energy_spent -= energy_delta;
+        } else {
+            stats.energy_regenerated += energy_delta;
+        }
+
+        stats.health_lost += a.health.saturating_sub(b.health) as u32;
+
+        let height_delta = b.loc.2 - a.loc.2;
+        if height_delta > 0.0 {
+            stats.height_gain += height_delta;
+        } else {
+            stats.height_loss -= height_delta;
+        }
+    }
+
+    if stats.duration > 0.0 {
+        stats.average_speed = weighted_speed / stats.duration;
+        stats.ski_ratio = stats.time_skiing / stats.duration;
+        stats.jet_ratio = stats.time_jetting / stats.duration;
+    } else {
+        stats.average_speed = positions.iter().map(speed).sum::<f32>() / positions.len() as f32;
+    }
+
+    stats
+}
+
+/// The speed of a sample from its velocity.
+pub(crate) fn speed(position: &Position) -> f32 {
+    let (x, y, z) = position.vel;
+    (x * x + y * y + z * z).sqrt()
+}
+
+/// The straight line distance between two locations.
+pub(crate) fn distance(a: (f32, f32, f32), b: (f32, f32, f32)) -> f32 {
+    let (dx, dy, dz) = (b.0 - a.0, b.1 - a.1, b.2 - a.2);
+    (dx * dx + dy * dy + dz * dz).sqrt()
+}
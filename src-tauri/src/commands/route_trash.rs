@@ -0,0 +1,259 @@
+use super::data::{get_app_local_data_dir, move_file, CONFIG_DIR};
+use super::route_names::parse_route_file_name;
+use super::routes::Route;
+use chrono::{DateTime, Duration, Local, NaiveDateTime, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use tauri::AppHandle;
+
+/// Folder in the app local data directory that deleted routes are moved to.
+const ROUTE_TRASH_DIR: &str = "route_trash";
+
+/// File in the app local data directory that holds the trash settings.
+const ROUTE_TRASH_SETTINGS_FILE: &str = "route_trash.json";
+
+/// Format of the deletion time prefixed to routes in the trash.
+const DELETED_FORMAT: &str = "%Y%m%d%H%M%S%3f";
+
+/// Days a deleted route is kept when no age has been set.
+const DEFAULT_MAX_AGE_DAYS: u32 = 30;
+
+/// Most days a deleted route can be kept for, about 100 years. Use 0 to keep them forever.
+const MAX_AGE_DAYS: u32 = 36_500;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RouteTrashSettings {
+    /// Days to keep deleted routes before purging them, 0 keeps them forever
+    max_age_days: u32,
+}
+
+impl Default for RouteTrashSettings {
+    fn default() -> Self {
+        RouteTrashSettings {
+            max_age_days: DEFAULT_MAX_AGE_DAYS,
+        }
+    }
+}
+
+/// A route in the trash.
+#[derive(Debug, Serialize)]
+pub struct DeletedRoute {
+    /// The name of the route in the trash, used to restore it
+    id: String,
+    /// The original route file name
+    file_name: String,
+    /// When the route was deleted, in RFC 3339
+    deleted: String,
+    route: Option<Route>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RouteTrash {
+    max_age_days: u32,
+    routes: Vec<DeletedRoute>,
+}
+
+/// List the routes in the trash, newest first, after purging any that are too old.
+///
+/// # Arguments
+///
+/// * `handle` - The AppHandle object
+///
+/// # Returns
+///
+/// * `Result<RouteTrash, String>` - The deleted routes and the purge age or an error message
+#[tauri::command]
+pub fn list_deleted_routes(handle: AppHandle) -> Result<RouteTrash, String> {
+    purge_route_trash(&handle)?;
+
+    let mut routes: Vec<DeletedRoute> = trash_entries(&handle)?
+        .into_iter()
+        .map(|(id, file_name, deleted)| DeletedRoute {
+            route: parse_route_file_name(&file_name).ok(),
+            deleted: deleted.to_rfc3339(),
+            id,
+            file_name,
+        })
+        .collect();
+    routes.sort_by(|a, b| b.id.cmp(&a.id));
+
+    Ok(RouteTrash {
+        max_age_days: load_settings(&handle).max_age_days,
+        routes,
+    })
+}
+
+/// Move a route from the trash back into the routes directory.
+///
+/// # Arguments
+///
+/// * `handle` - The AppHandle object
+/// * `id` - The name of the route in the trash
+///
+/// # Returns
+///
+/// * `Result<String, String>` - The restored route file name or an error message
+#[tauri::command]
+pub fn restore_route(handle: AppHandle, id: String) -> Result<String, String> {
+    let Some((_, file_name)) = split_trash_name(&id) else {
+        return Err(format!("{} is not a deleted route", id));
+    };
+    let trash_path = trash_dir(&handle).join(&id);
+    if !trash_path.is_file() {
+        return Err(format!("{} is not in the trash", id));
+    }
+
+    let routes_path = CONFIG_DIR.join("routes");
+    fs::create_dir_all(&routes_path)
+        .map_err(|e| format!("Failed to create routes directory: {}", e))?;
+    let target = routes_path.join(file_name);
+    if target.exists() {
+        return Err(format!("Route {} already exists", file_name));
+    }
+
+    move_file(&trash_path, &target)
+        .map_err(|e| format!("Failed to restore route {}: {}", file_name, e))?;
+
+    Ok(file_name.to_string())
+}
+
+/// Permanently delete every route in the trash.
+///
+/// # Arguments
+///
+/// * `handle` - The AppHandle object
+///
+/// # Returns
+///
+/// * `Result<usize, String>` - The number of routes deleted or an error message
+#[tauri::command]
+pub fn empty_route_trash(handle: AppHandle) -> Result<usize, String> {
+    remove_trash_entries(&handle, |_| true)
+}
+
+/// Set how many days deleted routes are kept before they are purged.
+///
+/// # Arguments
+///
+/// * `handle` - The AppHandle object
+/// * `max_age_days` - Days to keep deleted routes, at most 36500, 0 keeps them forever
+///
+/// # Returns
+///
+/// * `Result<usize, String>` - The number of routes purged with the new age or an error message
+#[tauri::command]
+pub fn set_route_trash_max_age(handle: AppHandle, max_age_days: u32) -> Result<usize, String> {
+    if max_age_days > MAX_AGE_DAYS {
+        return Err(format!(
+            "Routes can be kept for at most {} days, or 0 to keep them forever",
+            MAX_AGE_DAYS
+        ));
+    }
+
+    let app_data_dir = get_app_local_data_dir(&handle);
+    fs::create_dir_all(&app_data_dir)
+        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+
+    let settings_path = app_data_dir.join(ROUTE_TRASH_SETTINGS_FILE);
+    let content =
+        serde_json::to_string(&RouteTrashSettings { max_age_days }).map_err(|e| e.to_string())?;
+    fs::write(&settings_path, content)
+        .map_err(|e| format!("Failed to save route trash settings: {}", e))?;
+
+    purge_route_trash(&handle)
+}
+
+/// Move a route from the routes directory into the trash.
+pub(crate) fn move_route_to_trash(handle: &AppHandle, file: &str) -> Result<(), String> {
+    let file_path = CONFIG_DIR.join("routes").join(file);
+    if !file_path.is_file() {
+        return Err("File does not exist".into());
+    }
+
+    let trash = trash_dir(handle);
+    fs::create_dir_all(&trash)
+        .map_err(|e| format!("Failed to create route trash directory: {}", e))?;
+
+    let deleted = Local::now().format(DELETED_FORMAT);
+    move_file(&file_path, &trash.join(format!("{}_{}", deleted, file)))
+        .map_err(|e| format!("Failed to delete file: {}", e))
+}
+
+/// Permanently delete routes that have been in the trash longer than the configured age.
+///
+/// # Returns
+///
+/// * `Result<usize, String>` - The number of routes purged or an error message
+pub(crate) fn purge_route_trash(handle: &AppHandle) -> Result<usize, String> {
+    let max_age_days = load_settings(handle).max_age_days;
+    if max_age_days == 0 {
+        return Ok(0);
+    }
+
+    // An age too large to subtract from now keeps every route, like 0 does
+    let Some(cutoff) = Duration::try_days(max_age_days as i64)
+        .and_then(|max_age| Local::now().checked_sub_signed(max_age))
+    else {
+        return Ok(0);
+    };
+    remove_trash_entries(handle, |deleted| deleted < cutoff)
+}
+
+fn remove_trash_entries(
+    handle: &AppHandle,
+    should_remove: impl Fn(DateTime<Local>) -> bool,
+) -> Result<usize, String> {
+    let trash = trash_dir(handle);
+    let mut removed = 0;
+    for (id, _, deleted) in trash_entries(handle)? {
+        if should_remove(deleted) {
+            fs::remove_file(trash.join(&id))
+                .map_err(|e| format!("Failed to delete {}: {}", id, e))?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+/// Every route in the trash as its trash name, original file name and deletion time.
+fn trash_entries(handle: &AppHandle) -> Result<Vec<(String, String, DateTime<Local>)>, String> {
+    let trash = trash_dir(handle);
+    if !trash.exists() {
+        return Ok(Vec::new());
+    }
+
+    let entries = fs::read_dir(&trash).map_err(|e| format!("Failed to read route trash: {}", e))?;
+
+    Ok(entries
+        .flatten()
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| {
+            let id = entry.file_name().to_str()?.to_string();
+            let (deleted, file_name) = split_trash_name(&id)?;
+            let deleted = NaiveDateTime::parse_from_str(deleted, DELETED_FORMAT).ok()?;
+            let deleted = Local.from_local_datetime(&deleted).earliest()?;
+            Some((id.clone(), file_name.to_string(), deleted))
+        })
+        .collect())
+}
+
+/// Split a trash name into its deletion time and original file name.
+fn split_trash_name(id: &str) -> Option<(&str, &str)> {
+    if id.contains(['/', '\\']) {
+        return None;
+    }
+    id.split_once('_')
+        .filter(|(_, file_name)| !file_name.is_empty())
+}
+
+fn trash_dir(handle: &AppHandle) -> PathBuf {
+    get_app_local_data_dir(handle).join(ROUTE_TRASH_DIR)
+}
+
+fn load_settings(handle: &AppHandle) -> RouteTrashSettings {
+    fs::read_to_string(get_app_local_data_dir(handle).join(ROUTE_TRASH_SETTINGS_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
@@ -0,0 +1,272 @@
+use super::data::CONFIG_DIR;
+use super::route_names::{
+    build_route_file_name, parse_route_file_name, route_file_name, DEFAULT_GAME_MODE,
+};
+use super::routes::{encode_route_file, load_route, Position, RouteData};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum Severity {
+    /// The route is damaged and tools may misread it
+    Error,
+    /// The route is readable but something looks off
+    Warning,
+}
+
+/// A problem found in a route file.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind")]
+pub enum RouteProblem {
+    /// Bytes after the last complete position, usually from a crash while recording
+    TrailingBytes { count: usize },
+    /// The header position count does not match the positions in the file
+    LengthMismatch { header: u32, decoded: usize },
+    /// A position holds NaN or infinity
+    NonFiniteValue { index: usize, field: &'static str },
+    /// A position was recorded at or before the time of the one before it
+    NonMonotonicTime {
+        index: usize,
+        time: f32,
+        previous: f32,
+    },
+    /// A position has negative energy
+    NegativeEnergy { index: usize, energy: f32 },
+    /// The flag grab time is outside the recorded positions
+    FlagGrabOutOfRange { flag_grab_time: f32 },
+    /// The file has no positions at all
+    NoPositions,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RouteDiagnostic {
+    severity: Severity,
+    problem: RouteProblem,
+    message: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ValidationReport {
+    file_name: String,
+    valid: bool,
+    position_count: usize,
+    diagnostics: Vec<RouteDiagnostic>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RepairResult {
+    /// The file name of the repaired copy
+    file_name: String,
+    /// The number of positions that were dropped
+    dropped_positions: usize,
+    /// The report for the repaired copy
+    report: ValidationReport,
+}
+
+/// Check a route file for damage and impossible values.
+///
+/// # Arguments
+///
+/// * `file` - The route file name in the routes directory
+///
+/// # Returns
+///
+/// * `Result<ValidationReport, String>` - The diagnostics report, or an error message if the
+///   header itself cannot be read
+#[tauri::command]
+pub fn validate_route(file: String) -> Result<ValidationReport, String> {
+    let route = load_route(&file)?;
+    Ok(validate_route_data(&file, &route))
+}
+
+/// Write a repaired copy of a route next to the original.
+///
+/// Drops positions with impossible values or out of order times, drops any incomplete
+/// trailing record and fixes the position count in the header.
+///
+/// # Arguments
+///
+/// * `file` - The route file name in the routes directory
+///
+/// # Returns
+///
+/// * `Result<RepairResult, String>` - The repaired copy and its report or an error message
+#[tauri::command]
+pub fn repair_route(file: String) -> Result<RepairResult, String> {
+    let mut route = load_route(&file)?;
+    let dropped_positions = repair_route_data(&mut route);
+
+    let file_name = match parse_route_file_name(&file) {
+        Ok(mut parsed) => {
+            parsed.route_name = format!("{}-repaired", parsed.route_name);
+            build_route_file_name(&parsed)
+        }
+        Err(_) => route_file_name(DEFAULT_GAME_MODE, &route),
+    };
+    let file_path = CONFIG_DIR.join("routes").join(&file_name);
+    if file_path.exists() {
+        return Err(format!("Route {} already exists", file_name));
+    }
+
+    encode_route_file(&file_path, &route)
+        .map_err(|e| format!("Failed to write repaired route: {}", e))?;
+
+    Ok(RepairResult {
+        report: validate_route_data(&file_name, &route),
+        file_name,
+        dropped_positions,
+    })
+}
+
+/// Build the diagnostics report for a decoded route.
+pub(crate) fn validate_route_data(file_name: &str, route: &RouteData) -> ValidationReport {
+    let mut diagnostics = Vec::new();
+    let mut report = |severity: Severity, problem: RouteProblem| {
+        diagnostics.push(RouteDiagnostic {
+            severity,
+            message: describe_problem(&problem),
+            problem,
+        })
+    };
+
+    let trailing = route.trailing_bytes().len();
+    if trailing > 0 {
+        report(
+            Severity::Warning,
+            RouteProblem::TrailingBytes { count: trailing },
+        );
+    }
+
+    if route.route_length as usize != route.positions.len() {
+        report(
+            Severity::Warning,
+            RouteProblem::LengthMismatch {
+                header: route.route_length,
+                decoded: route.positions.len(),
+            },
+        );
+    }
+
+    if route.positions.is_empty() {
+        report(Severity::Error, RouteProblem::NoPositions);
+    }
+
+    let mut previous: Option<f32> = None;
+    for (index, position) in route.positions.iter().enumerate() {
+        if let Some(field) = non_finite_field(position) {
+            report(
+                Severity::Error,
+                RouteProblem::NonFiniteValue { index, field },
+            );
+            continue;
+        }
+        if let Some(previous) = previous.filter(|previous| position.time <= *previous) {
+            report(
+                Severity::Error,
+                RouteProblem::NonMonotonicTime {
+                    index,
+                    time: position.time,
+                    previous,
+                },
+            );
+        }
+        if position.energy < 0.0 {
+            report(
+                Severity::Error,
+                RouteProblem::NegativeEnergy {
+                    index,
+                    energy: position.energy,
+                },
+            );
+        }
+        previous = Some(position.time);
+    }
+
+    if let (Some(first), Some(last)) = (route.positions.first(), route.positions.last()) {
+        let grab = route.flag_grab_time;
+        if !grab.is_finite() || grab < first.time || grab > last.time {
+            report(
+                Severity::Warning,
+                RouteProblem::FlagGrabOutOfRange {
+                    flag_grab_time: grab,
+                },
+            );
+        }
+    }
+
+    ValidationReport {
+        file_name: file_name.to_string(),
+        valid: !diagnostics.iter().any(|d| d.severity == Severity::Error),
+        position_count: route.positions.len(),
+        diagnostics,
+    }
+}
+
+/// Repair a decoded route in place and return how many positions were dropped.
+pub(crate) fn repair_route_data(route: &mut RouteData) -> usize {
+    let before = route.positions.len();
+
+    let mut previous: Option<f32> = None;
+    route.positions.retain(|position| {
+        let keep = non_finite_field(position).is_none()
+            && position.energy >= 0.0
+            && previous.is_none_or(|previous| position.time > previous);
+        if keep {
+            previous = Some(position.time);
+        }
+        keep
+    });
+
+    route.drop_trailing_bytes();
+    route.route_length = route.positions.len() as u32;
+
+    before - route.positions.len()
+}
+
+/// The first field of a position that is NaN or infinite.
+fn non_finite_field(position: &Position) -> Option<&'static str> {
+    let fields = [
+        ("time", position.time),
+        ("loc.x", position.loc.0),
+        ("loc.y", position.loc.1),
+        ("loc.z", position.loc.2),
+        ("vel.x", position.vel.0),
+        ("vel.y", position.vel.1),
+        ("vel.z", position.vel.2),
+        ("energy", position.energy),
+    ];
+    fields
+        .into_iter()
+        .find(|(_, value)| !value.is_finite())
+        .map(|(field, _)| field)
+}
+
+fn describe_problem(problem: &RouteProblem) -> String {
+    match problem {
+        RouteProblem::TrailingBytes { count } => {
+            format!("{} bytes after the last complete position", count)
+        }
+        RouteProblem::LengthMismatch { header, decoded } => format!(
+            "Header says {} positions but the file has {}",
+            header, decoded
+        ),
+        RouteProblem::NonFiniteValue { index, field } => {
+            format!("Position {} has an invalid {}", index, field)
+        }
+        RouteProblem::NonMonotonicTime {
+            index,
+            time,
+            previous,
+        } => format!(
+            "Position {} is at {}s, not after the previous position at {}s",
+            index, time, previous
+        ),
+        RouteProblem::NegativeEnergy { index, energy } => {
+            format!("Position {} has negative energy ({})", index, energy)
+        }
+        RouteProblem::FlagGrabOutOfRange { flag_grab_time } => format!(
+            "Flag grab time {}s is outside the recorded positions",
+            flag_grab_time
+        ),
+        RouteProblem::NoPositions => "Route has no positions".to_string(),
+    }
+}
@@ -0,0 +1,220 @@
+use super::data::CONFIG_DIR;
+use super::personal_bests::update_personal_bests;
+use super::route_names::parse_route_file_name;
+use log::{debug, warn};
+use std::collections::HashMap;
+use std::fs;
+use std::thread;
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Emitter};
+
+/// How often the routes directory is checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The state of a route file when it was last seen.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct FileState {
+    modified: Option<SystemTime>,
+    size: u64,
+}
+
+/// Start watching the routes directory for changes in the background.
+///
+/// The directory is polled rather than watched through the OS file notification APIs.
+/// Those would need another dependency and behave differently per platform (and not at
+/// all on some network drives), while listing one directory once a second is cheap and
+/// only reads file metadata. Polling and decoding new routes block, so the watcher runs
+/// on its own thread rather than the async runtime.
+///
+/// TAMods writes a recording over several polls, so a file is only announced once its
+/// size and modification time are unchanged between two polls.
+///
+/// # Arguments
+///
+/// * `handle` - The AppHandle object
+///
+/// # Emit
+///
+/// * `route-added` - When a new route file appears, with the parsed `Route`
+/// * `route-removed` - When a route file is deleted, with the parsed `Route`
+/// * `route-modified` - When a route file is rewritten, with the parsed `Route`
+/// * `personal-best` - When a new route sets or beats a personal best, with the `PersonalBest`
+pub fn start_route_watcher(handle: AppHandle) {
+    let watcher = thread::Builder::new().name("route-watcher".into());
+    let spawned = watcher.spawn(move || {
+        let mut known = scan_routes();
+        let mut previous = known.clone();
+
+        // Catch up on routes recorded while the launcher was closed without announcing them
+        if let Err(e) = update_personal_bests(&handle) {
+            warn!("Failed to update personal bests: {}", e);
+        }
+        loop {
+            thread::sleep(POLL_INTERVAL);
+
+            let current = scan_routes();
+            let mut added = false;
+            for (event, file_name) in settled_changes(&mut known, &previous, &current) {
+                added |= event == "route-added";
+                emit_route_event(&handle, event, &file_name);
+            }
+            previous = current;
+
+            if added {
+                emit_personal_bests(&handle);
+            }
+        }
+    });
+    if let Err(e) = spawned {
+        warn!("Failed to start route watcher: {}", e);
+    }
+}
+
+/// Work out which route files were added, modified or removed since they were last
+/// announced, and record their new state in `known`.
+///
+/// A file that changed between `previous` and `current` is still being written and is
+/// left for a later poll. Removals are reported straight away.
+fn settled_changes(
+    known: &mut HashMap<String, FileState>,
+    previous: &HashMap<String, FileState>,
+    current: &HashMap<String, FileState>,
+) -> Vec<(&'static str, String)> {
+    let mut changes = Vec::new();
+    for (file_name, state) in current {
+        if previous.get(file_name) != Some(state) {
+            continue;
+        }
+        match known.insert(file_name.clone(), *state) {
+            None => changes.push(("route-added", file_name.clone())),
+            Some(announced) if announced != *state => {
+                changes.push(("route-modified", file_name.clone()))
+            }
+            _ => {}
+        }
+    }
+
+    let removed: Vec<String> = known
+        .keys()
+        .filter(|file_name| !current.contains_key(*file_name))
+        .cloned()
+        .collect();
+    for file_name in removed {
+        known.remove(&file_name);
+        changes.push(("route-removed", file_name));
+    }
+
+    changes
+}
+
+/// Read the current state of every route file in the routes directory.
+fn scan_routes() -> HashMap<String, FileState> {
+    let Ok(entries) = fs::read_dir(CONFIG_DIR.join("routes")) else {
+        return HashMap::new();
+    };
+
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension().is_none_or(|ext| ext != "route") {
+                return None;
+            }
+            let metadata = entry.metadata().ok().filter(|m| m.is_file())?;
+            let file_name = path.file_name()?.to_str()?.to_string();
+            let state = FileState {
+                modified: metadata.modified().ok(),
+                size: metadata.len(),
+            };
+            Some((file_name, state))
+        })
+        .collect()
+}
+
+fn emit_route_event(handle: &AppHandle, event: &str, file_name: &str) {
+    match parse_route_file_name(file_name) {
+        Ok(route) => {
+            if let Err(e) = handle.emit(event, route) {
+                warn!("Failed to emit {} for {}: {}", event, file_name, e);
+            }
+        }
+        Err(e) => debug!("Ignoring {} for {}: {}", event, file_name, e),
+    }
+}
+
+fn emit_personal_bests(handle: &AppHandle) {
+    match update_personal_bests(handle) {
+        Ok(bests) => {
+            for best in bests {
+                if let Err(e) = handle.emit("personal-best", best) {
+                    warn!("Failed to emit personal-best: {}", e);
+                }
+            }
+        }
+        Err(e) => warn!("Failed to update personal bests: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(size: u64) -> FileState {
+        FileState {
+            modified: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(size)),
+            size,
+        }
+    }
+
+    fn scan(files: &[(&str, u64)]) -> HashMap<String, FileState> {
+        files
+            .iter()
+            .map(|(file_name, size)| (file_name.to_string(), state(*size)))
+            .collect()
+    }
+
+    #[test]
+    fn routes_are_announced_once_they_stop_growing() {
+        let file = "ArxNovena_DS_LGT_60.00_Player.route";
+        let mut known = HashMap::new();
+        let polls = [
+            scan(&[]),
+            scan(&[(file, 100)]),
+            scan(&[(file, 200)]),
+            scan(&[(file, 300)]),
+            scan(&[(file, 300)]),
+            scan(&[(file, 300)]),
+        ];
+
+        let changes: Vec<Vec<(&str, String)>> = polls
+            .windows(2)
+            .map(|poll| settled_changes(&mut known, &poll[0], &poll[1]))
+            .collect();
+
+        assert!(changes[..3].iter().all(Vec::is_empty));
+        assert_eq!(changes[3], vec![("route-added", file.to_string())]);
+        assert!(changes[4].is_empty());
+    }
+
+    #[test]
+    fn rewrites_and_removals_are_announced_once() {
+        let file = "ArxNovena_DS_LGT_60.00_Player.route";
+        let mut known = scan(&[(file, 100)]);
+
+        assert!(
+            settled_changes(&mut known, &scan(&[(file, 100)]), &scan(&[(file, 150)])).is_empty()
+        );
+        assert_eq!(
+            settled_changes(&mut known, &scan(&[(file, 150)]), &scan(&[(file, 150)])),
+            vec![("route-modified", file.to_string())]
+        );
+        assert!(
+            settled_changes(&mut known, &scan(&[(file, 150)]), &scan(&[(file, 150)])).is_empty()
+        );
+        assert_eq!(
+            settled_changes(&mut known, &scan(&[(file, 150)]), &scan(&[])),
+            vec![("route-removed", file.to_string())]
+        );
+        assert!(known.is_empty());
+    }
+}
@@ -1,170 +1,271 @@
-use crate::commands::data::CREATE_NO_WINDOW;
-
 use super::data::CONFIG_DIR;
-use serde::Serialize;
-use std::convert::TryInto;
-use std::env;
+use super::map_registry::find_map_info;
+use super::route_names::{parse_route_file_name, RouteNameError};
+use super::route_simplify::simplify_route;
+use super::route_trash::{move_route_to_trash, purge_route_trash};
+use log::warn;
+use serde::{Deserialize, Serialize};
 use std::fs::{self, File};
-use std::io::{self, BufReader, Read};
-use std::os::windows::process::CommandExt;
+use std::io::{self, BufReader, BufWriter, Read, Write};
 use std::path::Path;
-use std::process::Command;
-use tauri::command;
+use tauri::{command, AppHandle};
 
-#[derive(Debug, serde::Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Route {
-    game_mode: String,
-    map: String,
-    side: String,
-    class: String,
-    username: String,
-    route_name: String,
-    time: String,
+    pub(crate) game_mode: String,
+    pub(crate) map: String,
+    pub(crate) side: String,
+    pub(crate) class: String,
+    pub(crate) username: String,
+    pub(crate) route_name: String,
+    pub(crate) time: String,
+    pub(crate) file_name: String,
+}
+
+/// A route file whose name could not be parsed.
+#[derive(Debug, Serialize)]
+pub struct UnparsedRoute {
     file_name: String,
+    error: RouteNameError,
+    message: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RouteFiles {
+    pub(crate) routes: Vec<Route>,
+    unparsed: Vec<UnparsedRoute>,
 }
 
 #[command]
-pub fn get_route_files() -> Result<Vec<Route>, String> {
+pub fn get_route_files() -> Result<RouteFiles, String> {
     let routes_path = &CONFIG_DIR.join("routes");
 
     if !routes_path.exists() {
         return Err("Routes directory does not exist".into());
     }
 
-    match fs::read_dir(routes_path) {
-        Ok(entries) => {
-            let routes = entries
-                .filter_map(|entry| {
-                    let entry = entry.ok()?;
-                    let path = entry.path();
-                    if path.is_file() && path.extension()? == "route" {
-                        let file_name = path.file_name()?.to_str()?;
-                        parse_route_format(file_name)
-                    } else {
-                        None
-                    }
-                })
-                .collect();
-            Ok(routes)
+    let entries =
+        fs::read_dir(routes_path).map_err(|_| "Failed to read routes directory".to_string())?;
+
+    let mut routes = Vec::new();
+    let mut unparsed = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_file() || path.extension().is_none_or(|ext| ext != "route") {
+            continue;
+        }
+        let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+
+        match parse_route_file_name(file_name) {
+            Ok(route) => routes.push(route),
+            Err(error) => unparsed.push(UnparsedRoute {
+                file_name: file_name.to_string(),
+                message: error.to_string(),
+                error,
+            }),
         }
-        Err(_) => Err("Failed to read routes directory".into()),
     }
+
+    Ok(RouteFiles { routes, unparsed })
 }
 
-// This would have been a lot simpler if I hadn't forgotten brackets on my websites re-encoder for route descriptions.
-fn parse_route_format(file_name: &str) -> Option<Route> {
-    // Splitting the filename at the first '-' to extract the game mode
-    let (game_mode, rest) = file_name.split_once('-')?;
+/// Delete a route by moving it to the route trash, where it can be restored from.
+///
+/// # Arguments
+///
+/// * `handle` - The AppHandle object
+/// * `file` - The route file name in the routes directory
+///
+/// # Returns
+///
+/// * `Result<(), String>` - Ok if the route was moved to the trash or an error message
+#[command]
+pub fn delete_route_file(handle: AppHandle, file: String) -> Result<(), String> {
+    move_route_to_trash(&handle, &file)?;
 
-    // Further splitting the string to extract map, side, class, and the remaining part
-    let parts: Vec<&str> = rest.splitn(4, '_').collect();
-    if parts.len() < 4 {
-        return None;
+    // Deleting is a good time to clear out routes that have been in the trash too long
+    if let Err(e) = purge_route_trash(&handle) {
+        warn!("Failed to purge route trash: {}", e);
     }
+    Ok(())
+}
 
-    let map = parts[0];
-    let side = parts[1];
-    let class = parts[2];
-    let mut username = parts[3];
+/// The `.route` layouts the launcher understands, keyed on `route_file_version`.
+///
+/// To support a new TAMods format, add a variant, register its version in `VERSIONS`
+/// and give it its own header and position readers and writers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum RouteFormat {
+    /// Space terminated header strings followed by 52 byte position records
+    V1,
+}
 
-    // Check if the username contains '.route', indicating no brackets in the file name
-    let mut route_name = "";
-    let mut time = "";
-    if username.contains(".route") {
-        // Split the remaining part at the first '_' after the username
-        if let Some((usr, remaining)) = username.split_once('_') {
-            username = usr;
-            // Extract route name and time from the remaining part
-            if let Some((route, time_with_ext)) = remaining.rsplit_once('_') {
-                route_name = route;
-                time = time_with_ext.trim_end_matches(".route");
-            }
-        }
-    } else {
-        // Route name and time are enclosed in brackets
-        if let Some((usr, route_name_with_brackets)) = parts[3].split_once("_(") {
-            username = usr;
-            let (route, time_with_ext) = route_name_with_brackets
-                .rsplit_once(")_")
-                .unwrap_or((route_name_with_brackets, ""));
-            route_name = route;
-            time = time_with_ext.trim_end_matches(".route");
+impl RouteFormat {
+    /// The `route_file_version` written for each layout.
+    const VERSIONS: [(f32, RouteFormat); 1] = [(1.0, RouteFormat::V1)];
+
+    /// The layout for a file version, if it is one we know.
+    pub(crate) fn from_version(version: f32) -> Option<RouteFormat> {
+        Self::VERSIONS
+            .iter()
+            .find(|(known, _)| *known == version)
+            .map(|(_, format)| *format)
+    }
+
+    /// Size of a single position record.
+    pub(crate) fn position_size(self) -> usize {
+        match self {
+            RouteFormat::V1 => 52,
         }
     }
+}
 
-    Some(Route {
-        game_mode: game_mode.to_string(),
-        map: map.to_string(),
-        side: side.to_string(),
-        class: class.to_string(),
-        username: username.to_string(),
-        route_name: route_name.to_string(),
-        time: time.to_string(),
-        file_name: file_name.to_string(),
-    })
+/// The error for a file version with no known layout.
+fn unsupported_version(version: f32) -> io::Error {
+    let supported: Vec<String> = RouteFormat::VERSIONS
+        .iter()
+        .map(|(known, _)| known.to_string())
+        .collect();
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!(
+            "Unsupported route file version {} (supported: {})",
+            version,
+            supported.join(", ")
+        ),
+    )
 }
 
-#[command]
-pub fn delete_route_file(file: String) -> Result<(), String> {
-    let routes_path = &CONFIG_DIR.join("routes");
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Position {
+    pub(crate) time: f32,
+    pub(crate) loc: (f32, f32, f32),
+    pub(crate) vel: (f32, f32, f32),
+    pub(crate) pitch: i32,
+    pub(crate) yaw: i32,
+    pub(crate) phys: i32,
+    pub(crate) skiing: bool,
+    pub(crate) jetting: bool,
+    pub(crate) health: u8,
+    pub(crate) energy: f32,
+    pub(crate) eta: i32,
+    /// Unused byte at offset 47, kept so the record can be written back unchanged
+    #[serde(skip)]
+    pub(crate) padding: u8,
+    /// The skiing and jetting bytes as read, any non-zero value is true
+    #[serde(skip)]
+    pub(crate) raw_flags: [u8; 2],
+}
 
-    let file_path = routes_path.join(file);
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteData {
+    pub(crate) route_file_version: f32,
+    pub(crate) map_name: String,
+    pub(crate) class_abbr: String,
+    pub(crate) player_name: String,
+    pub(crate) description: String,
+    pub(crate) team_num: u8,
+    pub(crate) class_id: i32,
+    pub(crate) class_health: u32,
+    pub(crate) flag_grab_time: f32,
+    pub(crate) route_length: u32,
+    pub(crate) positions: Vec<Position>,
+    #[serde(skip)]
+    pub(crate) encoding: RouteEncoding,
+}
 
-    // Check if the file exists
-    if !file_path.exists() {
-        return Err("File does not exist".into());
+/// Raw bytes of a decoded route that are lost when converting to `RouteData`.
+///
+/// Only used by the encoder so that an unmodified route is written back byte for byte.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RouteEncoding {
+    /// The raw header strings in file order (map, class, player, description)
+    strings: Vec<RawCString>,
+    /// Any bytes after the last complete position record
+    trailing: Vec<u8>,
+}
+
+/// A header string exactly as it was read from the file.
+#[derive(Debug, Clone)]
+struct RawCString {
+    bytes: Vec<u8>,
+    terminator: u8,
+}
+
+impl RouteData {
+    /// Bytes after the last complete position record, left behind by a partly written file.
+    pub(crate) fn trailing_bytes(&self) -> &[u8] {
+        &self.encoding.trailing
     }
 
-    // Attempt to delete the file
-    match fs::remove_file(file_path) {
-        Ok(_) => Ok(()),
-        Err(e) => Err(format!("Failed to delete file: {}", e)),
+    /// Drop any incomplete position record so it is not written back out.
+    pub(crate) fn drop_trailing_bytes(&mut self) {
+        self.encoding.trailing.clear();
+    }
+
+    /// Check that the header strings can be written, as the file ends each one at the first
+    /// space or NUL.
+    pub(crate) fn check_header_strings(&self) -> Result<(), String> {
+        let strings = [
+            ("Map name", &self.map_name),
+            ("Class", &self.class_abbr),
+            ("Player name", &self.player_name),
+            ("Description", &self.description),
+        ];
+        for (name, value) in strings {
+            if value.contains('\0') {
+                return Err(format!("{} cannot contain NUL characters", name));
+            }
+            if value.contains(' ') {
+                return Err(format!("{} '{}' cannot contain spaces", name, value));
+            }
+        }
+        Ok(())
     }
 }
 
-#[derive(Debug, Serialize)]
-pub struct Position {
-    time: f32,
-    loc: (f32, f32, f32),
-    vel: (f32, f32, f32),
-    pitch: i32,
-    yaw: i32,
-    phys: i32,
-    skiing: bool,
-    jetting: bool,
-    health: u8,
-    energy: f32,
-    eta: i32,
+impl RawCString {
+    /// The bytes to write for `value`, reusing the original bytes if the value is unchanged.
+    fn bytes_for<'a>(&'a self, value: &'a str) -> &'a [u8] {
+        if String::from_utf8_lossy(&self.bytes) == value {
+            &self.bytes
+        } else {
+            value.as_bytes()
+        }
+    }
 }
 
-#[derive(Debug, Serialize)]
-pub struct RouteData {
-    route_file_version: f32,
-    map_name: String,
-    class_abbr: String,
-    player_name: String,
-    description: String,
-    team_num: u8,
-    class_id: i32,
-    class_health: u32,
-    flag_grab_time: f32,
-    route_length: u32,
-    positions: Vec<Position>,
-}
-
-fn decode_route_file(filename: &str) -> io::Result<RouteData> {
+pub(crate) fn decode_route_file(filename: &str) -> io::Result<RouteData> {
     let mut file = BufReader::new(File::open(filename)?);
+    read_route(&mut file)
+}
 
+/// Decode a route from any reader, using the layout for its file version.
+pub(crate) fn read_route<R: Read>(file: &mut R) -> io::Result<RouteData> {
     // Read the file version
     let mut buf = [0u8; 4];
     file.read_exact(&mut buf)?;
     let route_file_version = f32::from_le_bytes(buf);
 
+    match RouteFormat::from_version(route_file_version) {
+        Some(RouteFormat::V1) => read_route_v1(file, route_file_version),
+        None => Err(unsupported_version(route_file_version)),
+    }
+}
+
+/// Decode the rest of a version 1 route after the file version.
+fn read_route_v1<R: Read>(file: &mut R, route_file_version: f32) -> io::Result<RouteData> {
     // Read strings
-    let map_name = read_cstring(&mut file)?;
-    let class_abbr = read_cstring(&mut file)?;
-    let player_name = read_cstring(&mut file)?;
-    let description = read_cstring(&mut file)?;
+    let raw_strings = vec![
+        read_cstring(file)?,
+        read_cstring(file)?,
+        read_cstring(file)?,
+        read_cstring(file)?,
+    ];
+    let [map_name, class_abbr, player_name, description] =
+        [0, 1, 2, 3].map(|i| String::from_utf8_lossy(&raw_strings[i].bytes).to_string());
 
     // Read metadata
     let mut buf = [0u8; 1];
@@ -184,100 +285,12 @@ fn decode_route_file(filename: &str) -> io::Result<RouteData> {
     file.read_exact(&mut buf)?;
     let route_length = u32::from_le_bytes(buf);
 
-    // Decode positions
-    let mut positions = Vec::new();
-    loop {
-        let mut pos_buf = vec![0u8; 52];
-        if file.read_exact(&mut pos_buf).is_err() {
-            break; // Break if less than 52 bytes are available
-        }
-
-        // Unpack position
-        let time = f32::from_le_bytes(
-            pos_buf[0..4]
-                .try_into()
-                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
-        );
-        let loc = (
-            -f32::from_le_bytes(
-                pos_buf[4..8]
-                    .try_into()
-                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
-            ),
-            f32::from_le_bytes(
-                pos_buf[8..12]
-                    .try_into()
-                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
-            ),
-            f32::from_le_bytes(
-                pos_buf[12..16]
-                    .try_into()
-                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
-            ),
-        );
-        let vel = (
-            f32::from_le_bytes(
-                pos_buf[16..20]
-                    .try_into()
-                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
-            ),
-            f32::from_le_bytes(
-                pos_buf[20..24]
-                    .try_into()
-                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
-            ),
-            f32::from_le_bytes(
-                pos_buf[24..28]
-                    .try_into()
-                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
-            ),
-        );
-        let pitch = i32::from_le_bytes(
-            pos_buf[28..32]
-                .try_into()
-                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
-        );
-        let yaw = i32::from_le_bytes(
-            pos_buf[32..36]
-                .try_into()
-                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
-        );
-        let phys = i32::from_le_bytes(
-            pos_buf[36..40]
-                .try_into()
-                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
-        );
-
-        let skiing = pos_buf[40] != 0;
-        let jetting = pos_buf[41] != 0;
-        let health = pos_buf[42];
-        let energy = f32::from_le_bytes(
-            pos_buf[43..47]
-                .try_into()
-                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
-        );
-        let eta = i32::from_le_bytes(
-            pos_buf[48..52]
-                .try_into()
-                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
-        );
-
-        let position = Position {
-            time,
-            loc,
-            vel,
-            pitch,
-            yaw,
-            phys,
-            skiing,
-            jetting,
-            health,
-            energy,
-            eta,
-        };
-
-        positions.push(position);
-    }
+    // Decode positions, keeping whatever is left after the last full record
+    let mut rest = Vec::new();
+    file.read_to_end(&mut rest)?;
+    let mut records = rest.chunks_exact(RouteFormat::V1.position_size());
+    let positions = records.by_ref().map(read_position_v1).collect();
+    let trailing = records.remainder().to_vec();
 
     Ok(RouteData {
         route_file_version,
@@ -291,24 +304,124 @@ fn decode_route_file(filename: &str) -> io::Result<RouteData> {
         flag_grab_time,
         route_length,
         positions,
+        encoding: RouteEncoding {
+            strings: raw_strings,
+            trailing,
+        },
     })
 }
 
-fn read_cstring(file: &mut BufReader<File>) -> io::Result<String> {
-    let mut string = Vec::new();
+/// Unpack a single version 1 position record.
+fn read_position_v1(pos_buf: &[u8]) -> Position {
+    let f32_at = |offset: usize| f32::from_le_bytes(le_bytes(pos_buf, offset));
+    let i32_at = |offset: usize| i32::from_le_bytes(le_bytes(pos_buf, offset));
+
+    Position {
+        time: f32_at(0),
+        loc: (-f32_at(4), f32_at(8), f32_at(12)),
+        vel: (f32_at(16), f32_at(20), f32_at(24)),
+        pitch: i32_at(28),
+        yaw: i32_at(32),
+        phys: i32_at(36),
+        skiing: pos_buf[40] != 0,
+        jetting: pos_buf[41] != 0,
+        health: pos_buf[42],
+        energy: f32_at(43),
+        padding: pos_buf[47],
+        raw_flags: [pos_buf[40], pos_buf[41]],
+        eta: i32_at(48),
+    }
+}
+
+fn le_bytes(buf: &[u8], offset: usize) -> [u8; 4] {
+    [
+        buf[offset],
+        buf[offset + 1],
+        buf[offset + 2],
+        buf[offset + 3],
+    ]
+}
+
+fn read_cstring<R: Read>(file: &mut R) -> io::Result<RawCString> {
+    let mut bytes = Vec::new();
     loop {
         let mut buf = [0u8; 1];
         file.read_exact(&mut buf)?;
         if buf[0] == 0 || buf[0] == b' ' {
-            break;
+            return Ok(RawCString {
+                bytes,
+                terminator: buf[0],
+            });
         }
-        string.push(buf[0]);
+        bytes.push(buf[0]);
     }
-    Ok(String::from_utf8_lossy(&string).to_string())
 }
 
+/// A decoded route as sent to the frontend, optionally with a reduced path.
+#[derive(Debug, Serialize)]
+pub struct DecodedRoute {
+    #[serde(flatten)]
+    route: RouteData,
+    /// For a reduced path, the index in the original route of each kept position
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source_indices: Option<Vec<usize>>,
+}
+
+/// Decode a route, optionally reducing its path so long routes are cheap to chart.
+///
+/// # Arguments
+///
+/// * `file` - The route file name in the routes directory
+/// * `tolerance` - The furthest a dropped position may be from the reduced path, in Unreal units
+/// * `max_points` - The most positions to return
+///
+/// # Returns
+///
+/// * `Result<DecodedRoute, String>` - The decoded route or an error message. When reduced,
+///   `positions` only holds the kept positions and `source_indices` maps them back
 #[tauri::command]
-pub fn decode_route(file: String) -> Result<RouteData, String> {
+pub fn decode_route(
+    file: String,
+    tolerance: Option<f32>,
+    max_points: Option<usize>,
+) -> Result<DecodedRoute, String> {
+    let mut route = load_route(&file)?;
+    if tolerance.is_none() && max_points.is_none() {
+        return Ok(DecodedRoute {
+            route,
+            source_indices: None,
+        });
+    }
+
+    if tolerance.is_some_and(|t| !t.is_finite() || t < 0.0) {
+        return Err("Tolerance must be zero or greater".into());
+    }
+    if max_points.is_some_and(|max| max < 2) {
+        return Err("At least two points must be kept".into());
+    }
+
+    let indices = simplify_route(&route, tolerance, max_points);
+    route.positions = indices
+        .iter()
+        .map(|&index| route.positions[index].clone())
+        .collect();
+
+    Ok(DecodedRoute {
+        route,
+        source_indices: Some(indices),
+    })
+}
+
+/// Decode a route file from the routes directory by its file name.
+///
+/// # Arguments
+///
+/// * `file` - The route file name in the routes directory
+///
+/// # Returns
+///
+/// * `Result<RouteData, String>` - The decoded route or an error message
+pub(crate) fn load_route(file: &str) -> Result<RouteData, String> {
     let routes_path = &CONFIG_DIR.join("routes");
     let file_path = routes_path.join(file);
 
@@ -326,32 +439,150 @@ pub fn decode_route(file: String) -> Result<RouteData, String> {
     decode_route_file(file_path_str).map_err(|e| e.to_string())
 }
 
-#[command]
-pub fn python_route_decoder(file: String, axis: Option<String>) -> Result<String, String> {
-    println!("Python route decoder");
+pub(crate) fn encode_route_file(filename: &Path, data: &RouteData) -> io::Result<()> {
+    let mut file = BufWriter::new(File::create(filename)?);
+    write_route(&mut file, data)?;
+    file.flush()
+}
+
+/// Encode a route to any writer, using the layout for its file version.
+///
+/// Decoding a file and encoding the result again without changes gives identical bytes.
+fn write_route<W: Write>(file: &mut W, data: &RouteData) -> io::Result<()> {
+    match RouteFormat::from_version(data.route_file_version) {
+        Some(RouteFormat::V1) => write_route_v1(file, data),
+        None => Err(unsupported_version(data.route_file_version)),
+    }
+}
 
-    // Check if Python is installed
-    let python_check = Command::new("python").arg("--version").output();
+fn write_route_v1<W: Write>(file: &mut W, data: &RouteData) -> io::Result<()> {
+    // Write the file version
+    file.write_all(&data.route_file_version.to_le_bytes())?;
+
+    // Write strings
+    let strings = [
+        &data.map_name,
+        &data.class_abbr,
+        &data.player_name,
+        &data.description,
+    ];
+    for (i, value) in strings.into_iter().enumerate() {
+        match data.encoding.strings.get(i) {
+            Some(raw) => {
+                file.write_all(raw.bytes_for(value))?;
+                file.write_all(&[raw.terminator])?;
+            }
+            None => {
+                file.write_all(value.as_bytes())?;
+                file.write_all(b" ")?;
+            }
+        }
+    }
+
+    // Write metadata
+    file.write_all(&[data.team_num])?;
+    file.write_all(&data.class_id.to_le_bytes())?;
+    file.write_all(&data.class_health.to_le_bytes())?;
+    file.write_all(&data.flag_grab_time.to_le_bytes())?;
+    file.write_all(&data.route_length.to_le_bytes())?;
 
-    if python_check.is_err() {
-        return Err("Python is not installed on this system".to_string());
+    // Encode positions
+    for position in &data.positions {
+        file.write_all(&write_position_v1(position))?;
     }
 
-    // Default to "xy" if axis is not provided
-    let axis = axis.unwrap_or_else(|| "xy".to_string());
-    // Get the project root directory
-    let project_root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    file.write_all(&data.encoding.trailing)
+}
+
+/// Pack a single position into its version 1 record.
+fn write_position_v1(position: &Position) -> [u8; 52] {
+    let mut pos_buf = [0u8; 52];
+    pos_buf[0..4].copy_from_slice(&position.time.to_le_bytes());
+    // The decoder negates X, so it has to be flipped back when writing
+    pos_buf[4..8].copy_from_slice(&(-position.loc.0).to_le_bytes());
+    pos_buf[8..12].copy_from_slice(&position.loc.1.to_le_bytes());
+    pos_buf[12..16].copy_from_slice(&position.loc.2.to_le_bytes());
+    pos_buf[16..20].copy_from_slice(&position.vel.0.to_le_bytes());
+    pos_buf[20..24].copy_from_slice(&position.vel.1.to_le_bytes());
+    pos_buf[24..28].copy_from_slice(&position.vel.2.to_le_bytes());
+    pos_buf[28..32].copy_from_slice(&position.pitch.to_le_bytes());
+    pos_buf[32..36].copy_from_slice(&position.yaw.to_le_bytes());
+    pos_buf[36..40].copy_from_slice(&position.phys.to_le_bytes());
+    pos_buf[40] = flag_byte(position.raw_flags[0], position.skiing);
+    pos_buf[41] = flag_byte(position.raw_flags[1], position.jetting);
+    pos_buf[42] = position.health;
+    pos_buf[43..47].copy_from_slice(&position.energy.to_le_bytes());
+    pos_buf[47] = position.padding;
+    pos_buf[48..52].copy_from_slice(&position.eta.to_le_bytes());
+    pos_buf
+}
 
-    // Construct the path to the Python script
-    let script_path = project_root.join("scripts\\route_decoder_script.py");
+/// The byte for a flag, the one it was read as unless the flag was changed.
+fn flag_byte(raw: u8, flag: bool) -> u8 {
+    if (raw != 0) == flag {
+        raw
+    } else {
+        flag as u8
+    }
+}
 
-    println!("{}", script_path.display());
+/// Swap the `_DS_`/`_BE_` side marker in a route file name.
+fn switch_side_in_file_name(file_name: &str) -> Option<String> {
+    if file_name.contains("_DS_") {
+        Some(file_name.replacen("_DS_", "_BE_", 1))
+    } else if file_name.contains("_BE_") {
+        Some(file_name.replacen("_BE_", "_DS_", 1))
+    } else {
+        None
+    }
+}
 
-    // Canonicalize the script path to ensure consistent path separators
-    let script_path =
-        fs::canonicalize(script_path).map_err(|e| format!("Failed to find script: {}", e))?;
+/// Reflect positions on an axis (`x`, `y` or `xy`) around a centre point.
+fn mirror_positions(
+    positions: &mut [Position],
+    axis: &str,
+    (centre_x, centre_y): (f32, f32),
+) -> Result<(), String> {
+    let (flip_x, flip_y) = match axis {
+        "x" => (true, false),
+        "y" => (false, true),
+        "xy" | "yx" => (true, true),
+        _ => return Err(format!("Invalid mirror axis: {}", axis)),
+    };
+
+    for position in positions.iter_mut() {
+        if flip_x {
+            position.loc.0 = 2.0 * centre_x - position.loc.0;
+        }
+        if flip_y {
+            position.loc.1 = 2.0 * centre_y - position.loc.1;
+        }
+    }
+    Ok(())
+}
 
-    // Construct the file path
+/// Mirror a route for the opposite side of the map.
+///
+/// Flips the route positions on the chosen axis around the map's mirror centre, swaps the
+/// team number and saves the result next to the original with the side in the file name
+/// switched. An existing route with that name is never overwritten.
+///
+/// # Arguments
+///
+/// * `handle` - The AppHandle object
+/// * `file` - The route file name in the routes directory
+/// * `axis` - The axis to mirror on (`x`, `y` or `xy`), defaults to the map's axis in the
+///   map registry, or `xy` if the map is not known
+///
+/// # Returns
+///
+/// * `Result<String, String>` - The file name of the mirrored route or an error message
+#[command]
+pub fn mirror_route(
+    handle: AppHandle,
+    file: String,
+    axis: Option<String>,
+) -> Result<String, String> {
     let routes_path = &CONFIG_DIR.join("routes");
     let file_path = routes_path.join(&file);
 
@@ -360,67 +591,193 @@ pub fn python_route_decoder(file: String, axis: Option<String>) -> Result<String
         return Err(format!("File {} does not exist", file));
     }
 
-    let dir_path = file_path.parent().ok_or("Failed to get directory path")?;
-    let file_name = file_path
-        .file_name()
-        .ok_or("Failed to get file name")?
+    let mirrored_file = switch_side_in_file_name(&file)
+        .ok_or_else(|| format!("Could not find the side (DS/BE) in file name {}", file))?;
+    let mirrored_path = routes_path.join(&mirrored_file);
+    if mirrored_path.exists() {
+        return Err(format!("Route {} already exists", mirrored_file));
+    }
+
+    let file_path_str = file_path
         .to_str()
-        .ok_or("Failed to convert file name to string")?;
-
-    // Execute the Python script with directory path, file name, and axis as arguments
-    let output = Command::new("python")
-        .arg(
-            script_path
-                .to_str()
-                .ok_or("Failed to convert script path to string")?,
-        )
-        .arg(
-            dir_path
-                .to_str()
-                .ok_or("Failed to convert directory path to string")?,
-        )
-        .arg(file_name)
-        .arg(&axis)
-        .creation_flags(CREATE_NO_WINDOW)
-        .output()
-        .map_err(|e| e.to_string())?;
-
-    // Check execution status
-    if !output.status.success() {
-        return Err(String::from_utf8_lossy(&output.stderr).to_string());
-    }
-
-    Ok(String::from_utf8_lossy(&output.stdout).to_string())
-}
-
-// Check if Python is installed
-#[command]
-pub fn check_python_installed() -> Result<bool, String> {
-    // First, try to check if Python is in the system PATH
-    if let Ok(path) = env::var("PATH") {
-        for path in env::split_paths(&path) {
-            let python_path = path.join("python");
-            if python_path.exists() || python_path.with_extension("exe").exists() {
-                return Ok(true);
-            }
+        .ok_or("Failed to convert path to string")?;
+    let mut route = decode_route_file(file_path_str).map_err(|e| e.to_string())?;
+
+    // Fall back to the map's own transform, then to "xy" around the origin
+    let map_info = find_map_info(&handle, &route.map_name).unwrap_or_default();
+    let axis = axis
+        .or(map_info.mirror_axis)
+        .unwrap_or_else(|| "xy".to_string());
+    mirror_positions(
+        &mut route.positions,
+        &axis,
+        map_info.mirror_centre.unwrap_or((0.0, 0.0)),
+    )?;
+
+    // Swap team number
+    route.team_num = if route.team_num == 0 { 1 } else { 0 };
+
+    encode_route_file(&mirrored_path, &route)
+        .map_err(|e| format!("Failed to write mirrored route: {}", e))?;
+
+    Ok(mirrored_file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Space terminated strings, skiing and jetting as 0 and 1, no trailing bytes.
+    const SPACE_TERMINATED: &[u8] =
+        include_bytes!("../../tests/fixtures/routes/katabatic_space_terminated.route");
+    /// NUL terminated strings, a non-UTF-8 player name, an empty description, flag bytes
+    /// other than 0 and 1, set padding bytes and a partial record at the end.
+    const NUL_TERMINATED_TRAILING: &[u8] =
+        include_bytes!("../../tests/fixtures/routes/nul_terminated_trailing.route");
+
+    fn decode(bytes: &[u8]) -> RouteData {
+        read_route(&mut &bytes[..]).unwrap()
+    }
+
+    fn encode(route: &RouteData) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        write_route(&mut bytes, route).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn unchanged_routes_round_trip_byte_for_byte() {
+        for fixture in [SPACE_TERMINATED, NUL_TERMINATED_TRAILING] {
+            assert_eq!(encode(&decode(fixture)), fixture);
         }
     }
 
-    // If not found in PATH, try to run Python with CREATE_NO_WINDOW flag (Windows-specific)
-    #[cfg(target_os = "windows")]
-    {
-        use std::os::windows::process::CommandExt;
+    #[test]
+    fn decodes_space_terminated_header_and_positions() {
+        let route = decode(SPACE_TERMINATED);
+        assert_eq!(route.route_file_version, 1.0);
+        assert_eq!(route.map_name, "Katabatic");
+        assert_eq!(route.class_abbr, "LGT");
+        assert_eq!(route.player_name, "Runner");
+        assert_eq!(route.description, "Practice");
+        assert_eq!(route.team_num, 0);
+        assert_eq!(route.class_id, 1683);
+        assert_eq!(route.class_health, 800);
+        assert_eq!(route.flag_grab_time, 12.5);
+        assert_eq!(route.route_length, 3);
+        assert_eq!(route.positions.len(), 3);
+        assert!(route.trailing_bytes().is_empty());
+
+        let position = &route.positions[2];
+        assert_eq!(position.time, 0.2);
+        assert_eq!(position.loc, (-1176.0, 2152.0, 548.0));
+        assert_eq!(position.vel, (770.0, 530.0, 0.0));
+        assert_eq!(
+            (position.pitch, position.yaw, position.phys),
+            (-1400, 15800, 2)
+        );
+        assert!(position.skiing && position.jetting);
+        assert_eq!(position.health, 190);
+        assert_eq!(position.energy, 90.0);
+        assert_eq!(position.eta, 28);
+    }
+
+    #[test]
+    fn decodes_nul_terminated_header_with_trailing_bytes() {
+        let route = decode(NUL_TERMINATED_TRAILING);
+        assert_eq!(route.map_name, "Crossfire");
+        assert_eq!(route.class_abbr, "DMB");
+        assert_eq!(route.player_name, "J\u{fffd}ger");
+        assert_eq!(route.description, "");
+        assert_eq!(route.positions.len(), 2);
+        assert_eq!(route.trailing_bytes(), (1..18).collect::<Vec<u8>>());
+
+        let [first, second] = [&route.positions[0], &route.positions[1]];
+        assert!(first.skiing && first.jetting);
+        assert!(!second.skiing && second.jetting);
+    }
+
+    #[test]
+    fn dropping_trailing_bytes_only_removes_the_partial_record() {
+        let mut route = decode(NUL_TERMINATED_TRAILING);
+        route.drop_trailing_bytes();
+        assert_eq!(
+            encode(&route),
+            &NUL_TERMINATED_TRAILING[..NUL_TERMINATED_TRAILING.len() - 17]
+        );
+    }
+
+    #[test]
+    fn edited_strings_are_written_with_their_original_terminator() {
+        let mut route = decode(NUL_TERMINATED_TRAILING);
+        route.player_name = "Jager".to_string();
+        let bytes = encode(&route);
+        assert!(bytes.starts_with(b"\x00\x00\x80\x3fCrossfire\0DMB\0Jager\0\0"));
+
+        let route = decode(&bytes);
+        assert_eq!(route.player_name, "Jager");
+        assert_eq!(route.description, "");
+    }
+
+    #[test]
+    fn only_edited_flags_are_normalised() {
+        let mut route = decode(NUL_TERMINATED_TRAILING);
+        let header_size = NUL_TERMINATED_TRAILING.len() - 17 - 2 * 52;
+        route.positions[0].skiing = false;
+
+        let bytes = encode(&route);
+        let record = &bytes[header_size..header_size + 52];
+        assert_eq!(record[40], 0);
+        assert_eq!(record[41], 0xff);
+        assert_eq!(record[47], 0x5a);
+        assert_eq!(bytes[header_size + 52 + 41], 7);
+    }
 
-        match Command::new("python")
-            .arg("--version")
-            .creation_flags(CREATE_NO_WINDOW)
-            .output()
-        {
-            Ok(output) => return Ok(output.status.success()),
-            Err(_) => {}
+    #[test]
+    fn only_known_file_versions_are_accepted() {
+        assert_eq!(RouteFormat::VERSIONS.map(|(version, _)| version), [1.0]);
+        assert_eq!(RouteFormat::from_version(1.0), Some(RouteFormat::V1));
+
+        for version in [0.0, 0.5, 1.0001, 2.0, f32::NAN] {
+            let mut bytes = SPACE_TERMINATED.to_vec();
+            bytes[..4].copy_from_slice(&version.to_le_bytes());
+            let error = read_route(&mut &bytes[..]).unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+            assert!(error.to_string().contains("(supported: 1)"), "{}", error);
+
+            let mut route = decode(SPACE_TERMINATED);
+            route.route_file_version = version;
+            assert!(write_route(&mut Vec::new(), &route).is_err());
         }
     }
 
-    // If all else fails, assume Python is not installed
-    Ok(false)
+    #[test]
+    fn header_strings_with_terminators_are_rejected() {
+        let mut route = decode(SPACE_TERMINATED);
+        assert!(route.check_header_strings().is_ok());
+
+        route.description = "two words".to_string();
+        assert!(route.check_header_strings().unwrap_err().contains("spaces"));
+        route.description = "nul\0".to_string();
+        assert!(route.check_header_strings().unwrap_err().contains("NUL"));
+    }
+
+    #[test]
+    fn mirroring_reflects_around_the_centre() {
+        let mut positions = decode(SPACE_TERMINATED).positions;
+        let (x, y, z) = positions[0].loc;
+
+        mirror_positions(&mut positions, "xy", (100.0, -50.0)).unwrap();
+        assert_eq!(positions[0].loc, (200.0 - x, -100.0 - y, z));
+
+        mirror_positions(&mut positions, "x", (0.0, 0.0)).unwrap();
+        assert_eq!(positions[0].loc, (x - 200.0, -100.0 - y, z));
+
+        assert!(mirror_positions(&mut positions, "z", (0.0, 0.0)).is_err());
+    }
+
+    #[test]
+    fn truncated_headers_fail_to_decode() {
+        assert!(read_route(&mut &SPACE_TERMINATED[..20]).is_err());
+    }
 }
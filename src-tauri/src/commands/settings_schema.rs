@@ -0,0 +1,291 @@
+use serde::Serialize;
+
+/// The kind of value a setting holds.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SettingType {
+    /// `True` or `False`
+    Boolean,
+    Integer,
+    Float,
+}
+
+/// A known setting in one of the game's config files.
+#[derive(Debug, Serialize)]
+pub struct SettingSchema {
+    pub file: &'static str,
+    /// Every section the key is read from, most keys are in one
+    pub sections: &'static [&'static str],
+    pub key: &'static str,
+    pub display_name: &'static str,
+    #[serde(rename = "type")]
+    pub setting_type: SettingType,
+    /// The smallest and largest values allowed for numbers, inclusive
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    /// The value in the launcher's default config file
+    pub default: &'static str,
+    pub description: &'static str,
+}
+
+/// The settings the launcher knows how to edit.
+pub const SETTINGS: [SettingSchema; 18] = [
+    SettingSchema {
+        file: "tribes.ini",
+        sections: &["TribesGame.TrGameEngine"],
+        key: "MaxSmoothedFrameRate",
+        display_name: "Max Framerate",
+        setting_type: SettingType::Float,
+        min: Some(1.0),
+        max: Some(1000.0),
+        default: "122",
+        description: "[MaxSmoothedFrameRate] Set the maximum framerate (requires framerate smoothing to be enabled).",
+    },
+    SettingSchema {
+        file: "tribes.ini",
+        sections: &["TribesGame.TrGameEngine"],
+        key: "bSmoothFrameRate",
+        display_name: "Framerate Smoothing",
+        setting_type: SettingType::Boolean,
+        min: None,
+        max: None,
+        default: "True",
+        description: "[bSmoothFrameRate] Allows custom framerate when enabled.",
+    },
+    SettingSchema {
+        file: "tribes.ini",
+        sections: &["TribesGame.TrDevice"],
+        key: "m_bTinyWeaponsEnabled",
+        display_name: "Reduce Weapon Size",
+        setting_type: SettingType::Boolean,
+        min: None,
+        max: None,
+        default: "True",
+        description: "[m_bTinyWeaponsEnabled] Enable or disable tiny weapons.",
+    },
+    SettingSchema {
+        file: "tribes.ini",
+        sections: &["SystemSettings"],
+        key: "DynamicLights",
+        display_name: "Dynamic Lights",
+        setting_type: SettingType::Boolean,
+        min: None,
+        max: None,
+        default: "True",
+        description: "[DynamicLights] Significantly improves visuals, but has a very large performance impact (up to -50%).",
+    },
+    SettingSchema {
+        file: "tribes.ini",
+        sections: &["SystemSettings"],
+        key: "DepthOfField",
+        display_name: "Post Processing",
+        setting_type: SettingType::Boolean,
+        min: None,
+        max: None,
+        default: "True",
+        description: "[DepthOfField] Enable or disable post-processing.",
+    },
+    SettingSchema {
+        file: "tribes.ini",
+        sections: &["SystemSettings"],
+        key: "Bloom",
+        display_name: "Bloom",
+        setting_type: SettingType::Boolean,
+        min: None,
+        max: None,
+        default: "True",
+        description: "[Bloom] Enable or disable bloom.",
+    },
+    SettingSchema {
+        file: "tribes.ini",
+        sections: &["SystemSettings"],
+        key: "MotionBlur",
+        display_name: "Motion Blur",
+        setting_type: SettingType::Boolean,
+        min: None,
+        max: None,
+        default: "False",
+        description: "[MotionBlur] Enable or disable motion blur.",
+    },
+    SettingSchema {
+        file: "tribes.ini",
+        sections: &["TribesGame.TrGameEngine", "Engine.Engine"],
+        key: "bForceStaticTerrain",
+        display_name: "Force Static Terrain",
+        setting_type: SettingType::Boolean,
+        min: None,
+        max: None,
+        default: "True",
+        description: "[bForceStaticTerrain]  Disables terrain popping when false.",
+    },
+    SettingSchema {
+        file: "tribes.ini",
+        sections: &["SystemSettings"],
+        key: "SpeedTreeLeaves",
+        display_name: "Tree Leaves",
+        setting_type: SettingType::Boolean,
+        min: None,
+        max: None,
+        default: "True",
+        description: "[SpeedTreeLeaves] Enable or disable leaves on trees.",
+    },
+    SettingSchema {
+        file: "tribes.ini",
+        sections: &["SystemSettings"],
+        key: "SpeedTreeFronds",
+        display_name: "Tree Branches",
+        setting_type: SettingType::Boolean,
+        min: None,
+        max: None,
+        default: "True",
+        description: "[SpeedTreeFronds] Enable or disable branches on some trees.",
+    },
+    SettingSchema {
+        file: "tribes.ini",
+        sections: &["SystemSettings"],
+        key: "AllowRadialBlur",
+        display_name: "Impact Distortion",
+        setting_type: SettingType::Boolean,
+        min: None,
+        max: None,
+        default: "True",
+        description: "[AllowRadialBlur] When false, removes some of the distortions from weapon impacts.",
+    },
+    SettingSchema {
+        file: "tribes.ini",
+        sections: &["SystemSettings"],
+        key: "OneFrameThreadLag",
+        display_name: "One Frame Thread Lag",
+        setting_type: SettingType::Boolean,
+        min: None,
+        max: None,
+        default: "False",
+        description: "[OneFrameThreadLag] Reduces mouse latency when disabled.",
+    },
+    SettingSchema {
+        file: "tribes.ini",
+        sections: &["SystemSettings"],
+        key: "UseVsync",
+        display_name: "Vsync",
+        setting_type: SettingType::Boolean,
+        min: None,
+        max: None,
+        default: "False",
+        description: "[UseVsync] Enable or disable Vsync.",
+    },
+    SettingSchema {
+        file: "tribes.ini",
+        sections: &["SystemSettings"],
+        key: "ResX",
+        display_name: "Res Width",
+        setting_type: SettingType::Integer,
+        min: Some(320.0),
+        max: Some(15360.0),
+        default: "1920",
+        description: "[ResX] Set the resolution width.",
+    },
+    SettingSchema {
+        file: "tribes.ini",
+        sections: &["SystemSettings"],
+        key: "ResY",
+        display_name: "Res Height",
+        setting_type: SettingType::Integer,
+        min: Some(240.0),
+        max: Some(8640.0),
+        default: "1080",
+        description: "[ResY] Set the resolution height.",
+    },
+    SettingSchema {
+        file: "TribesInput.ini",
+        sections: &["Engine.PlayerInput"],
+        key: "bEnableMouseSmoothing",
+        display_name: "Mouse Smoothing",
+        setting_type: SettingType::Boolean,
+        min: None,
+        max: None,
+        default: "False",
+        description: "[bEnableMouseSmoothing] Ties mouse sensitivity to framerate when enabled. Disable",
+    },
+    SettingSchema {
+        file: "TribesInput.ini",
+        sections: &["Engine.PlayerInput"],
+        key: "FOVSetting",
+        display_name: "Field of View",
+        setting_type: SettingType::Integer,
+        min: Some(80.0),
+        max: Some(120.0),
+        default: "120",
+        description: "[FOVSetting] Set the field of view (Max 120).",
+    },
+    SettingSchema {
+        file: "TribesInput.ini",
+        sections: &["Engine.PlayerInput"],
+        key: "MouseSensitivity",
+        display_name: "Mouse Sensitivity",
+        setting_type: SettingType::Float,
+        min: Some(0.0),
+        max: Some(100.0),
+        default: "10.000000",
+        description: "[MouseSensitivity] Set the mouse sensitivity (0-100).",
+    },
+];
+
+/// Get every setting the launcher knows how to edit.
+///
+/// # Returns
+///
+/// * `&'static [SettingSchema]` - The schema of each setting
+#[tauri::command]
+pub fn get_settings_schema() -> &'static [SettingSchema] {
+    &SETTINGS
+}
+
+/// Find the schema of a setting by its file and key, ignoring case.
+pub(crate) fn find_setting(file: &str, key: &str) -> Option<&'static SettingSchema> {
+    SETTINGS
+        .iter()
+        .find(|s| s.file.eq_ignore_ascii_case(file) && s.key.eq_ignore_ascii_case(key))
+}
+
+/// Check a value against a setting's schema.
+///
+/// # Returns
+///
+/// * `Result<String, String>` - The value as the game writes it (booleans become `True` or
+///   `False`) or an error message saying what is wrong with it
+pub(crate) fn validate_setting(setting: &SettingSchema, value: &str) -> Result<String, String> {
+    let value = value.trim();
+    let number = match setting.setting_type {
+        SettingType::Boolean => {
+            return match value.to_ascii_lowercase().as_str() {
+                "true" => Ok("True".to_string()),
+                "false" => Ok("False".to_string()),
+                _ => Err(format!(
+                    "{} must be True or False, not {}",
+                    setting.key, value
+                )),
+            };
+        }
+        SettingType::Integer => value
+            .parse::<i64>()
+            .map(|n| n as f64)
+            .map_err(|_| format!("{} must be a whole number, not {}", setting.key, value))?,
+        SettingType::Float => value
+            .parse::<f64>()
+            .ok()
+            .filter(|n| n.is_finite())
+            .ok_or_else(|| format!("{} must be a number, not {}", setting.key, value))?,
+    };
+
+    if setting.min.is_some_and(|min| number < min) || setting.max.is_some_and(|max| number > max) {
+        return Err(format!(
+            "{} must be between {} and {}, not {}",
+            setting.key,
+            setting.min.map_or("any".to_string(), |min| min.to_string()),
+            setting.max.map_or("any".to_string(), |max| max.to_string()),
+            value
+        ));
+    }
+
+    Ok(value.to_string())
+}
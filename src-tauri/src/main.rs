@@ -4,18 +4,39 @@
 mod commands;
 use commands::{
     config_backup_manager::{backup_ini_files, delete_backup, get_backups, load_backup_ini_file},
+    config_diff::diff_config,
     config_manager::{fetch_config_files, update_ini_file},
     config_preset_manager::{check_config, replace_config},
     directory_shortcuts::open_directory,
     fetch_player_counts::fetch_players_online,
     find_game_path::find_path,
+    key_bindings::{add_key_binding, list_key_bindings, rebind_key_binding, remove_key_binding},
+    keybind_profile::{export_keybinds, import_keybinds, render_keybind_cheat_sheet},
     launch_game::launch_game,
+    map_registry::{get_map_registry, remove_map_info, set_map_info},
     package_downloader::download_package,
     packages::fetch_packages,
-    routes::{
-        check_python_installed, decode_route, delete_route_file, get_route_files,
-        python_route_decoder,
+    personal_bests::{get_personal_bests, get_route_history},
+    route_batch::{
+        batch_delete_routes, batch_export_routes, batch_mirror_routes, batch_move_routes,
     },
+    route_bundle::{export_route_bundle, import_route_bundle},
+    route_compare::compare_routes,
+    route_editor::edit_route,
+    route_export::{export_route, import_route},
+    route_heatmap::route_heatmap,
+    route_index::query_routes,
+    route_playback::sample_routes,
+    route_preview::render_route_preview,
+    route_stats::route_stats,
+    route_trash::{
+        empty_route_trash, list_deleted_routes, purge_route_trash, restore_route,
+        set_route_trash_max_age,
+    },
+    route_validation::{repair_route, validate_route},
+    route_watcher::start_route_watcher,
+    routes::{decode_route, delete_route_file, get_route_files, mirror_route},
+    settings_schema::get_settings_schema,
 };
 
 fn main() {
@@ -28,6 +49,10 @@ fn main() {
             #[cfg(desktop)]
             app.handle()
                 .plugin(tauri_plugin_updater::Builder::new().build())?;
+            start_route_watcher(app.handle().clone());
+            if let Err(e) = purge_route_trash(app.handle()) {
+                log::warn!("Failed to purge route trash: {}", e);
+            }
             Ok(())
         })
         .plugin(tauri_plugin_dialog::init())
@@ -41,19 +66,53 @@ fn main() {
             replace_config,
             fetch_config_files,
             update_ini_file,
+            get_settings_schema,
+            list_key_bindings,
+            add_key_binding,
+            rebind_key_binding,
+            remove_key_binding,
+            export_keybinds,
+            import_keybinds,
+            render_keybind_cheat_sheet,
             download_package,
             fetch_players_online,
             launch_game,
             open_directory,
             delete_route_file,
             get_route_files,
+            list_deleted_routes,
+            restore_route,
+            empty_route_trash,
+            set_route_trash_max_age,
             decode_route,
-            python_route_decoder,
-            check_python_installed,
+            mirror_route,
+            batch_delete_routes,
+            batch_mirror_routes,
+            batch_export_routes,
+            batch_move_routes,
+            route_stats,
+            get_map_registry,
+            set_map_info,
+            remove_map_info,
+            compare_routes,
+            sample_routes,
+            route_heatmap,
+            render_route_preview,
+            get_personal_bests,
+            get_route_history,
+            export_route,
+            import_route,
+            query_routes,
+            edit_route,
+            export_route_bundle,
+            import_route_bundle,
+            validate_route,
+            repair_route,
             load_backup_ini_file,
             backup_ini_files,
             delete_backup,
             get_backups,
+            diff_config,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
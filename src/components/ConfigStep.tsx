@@ -3,24 +3,27 @@ import { ConfigCard } from "./ConfigCard";
 import { configPresets } from "../data/configPresets";
 import {
   fetchConfigFiles,
+  getSettingsFields,
   handleInputChange,
   handleSensitivityChange,
   openDirectory,
 } from "../utils/utils";
 import ConfigSettingsTable from "./ConfigSettingsTable";
-import { iniFields, inputIniFields } from "../data/iniFields";
 import SensitivityCalculator from "./SensitivityCalculator";
 import NotificationPopup from "./NotificationPopup";
 import { useEffect, useState } from "react";
-import { Notification } from "../interfaces";
+import { Field, Notification } from "../interfaces";
 import { useConfig } from "../contexts/ConfigContext";
 import { IoOpenOutline } from "react-icons/io5";
+import { IconX } from "@tabler/icons-react";
 
 const ConfigStep = () => {
   const { config } = useConfig();
   const [iniValues, setIniValues] = useState<{
     [key: string]: boolean | number;
   }>({});
+  const [iniFields, setIniFields] = useState<Field[]>([]);
+  const [inputIniFields, setInputIniFields] = useState<Field[]>([]);
   const allFields = [...iniFields, ...inputIniFields];
   const third = Math.ceil(allFields.length / 3); // Divides fields into 3 columns
   const [notification, setNotification] = useState<Notification>({
@@ -31,8 +34,35 @@ const ConfigStep = () => {
     icon: null,
   });
 
+  // Show why a setting was rejected, handleInputChange has already put it back
+  const showSettingError = (message: string) =>
+    setNotification({
+      visible: true,
+      message,
+      icon: <IconX />,
+      color: "red",
+      title: "Setting not saved",
+    });
+
+  // Save a setting to the sections the schema lists for it
+  const updateSetting = (
+    fileKey: string,
+    key: string,
+    value: boolean | number
+  ) =>
+    handleInputChange(
+      fileKey,
+      key,
+      value,
+      allFields.find((field) => field.key === key)?.sections ?? [],
+      setIniValues,
+      showSettingError
+    );
+
   useEffect(() => {
     fetchConfigFiles(setIniValues);
+    getSettingsFields("tribes.ini", setIniFields);
+    getSettingsFields("TribesInput.ini", setInputIniFields);
   }, []);
 
   return (
@@ -78,14 +108,14 @@ const ConfigStep = () => {
             fields={iniFields.slice(0, third)}
             iniValues={iniValues}
             handleInputChange={(key, value) =>
-              handleInputChange("main", key, value, setIniValues)
+              updateSetting("main", key, value)
             }
           />
           <ConfigSettingsTable
             fields={iniFields.slice(third, third * 2)}
             iniValues={iniValues}
             handleInputChange={(key, value) =>
-              handleInputChange("main", key, value, setIniValues)
+              updateSetting("main", key, value)
             }
           />
           <div style={{ display: "flex", flexDirection: "column", flex: 1 }}>
@@ -93,7 +123,7 @@ const ConfigStep = () => {
               fields={inputIniFields}
               iniValues={iniValues}
               handleInputChange={(key, value) =>
-                handleInputChange("input", key, value, setIniValues)
+                updateSetting("input", key, value)
               }
             />
             <div style={{ marginTop: "0.7rem", flexShrink: 0 }}>
@@ -129,8 +159,7 @@ const ConfigStep = () => {
               value,
               iniValues,
               config,
-              (fileKey, key, value) =>
-                handleInputChange(fileKey, key, value, setIniValues)
+              (fileKey, key, value) => updateSetting(fileKey, key, value)
             )
           }
         />
@@ -12,6 +12,7 @@ import {
   Center,
 } from "@mantine/core";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import LocationChart from "../RouteGraphs";
 import RouteFilters from "../RouteFilters";
 
@@ -26,6 +27,16 @@ interface Route {
   file_name: string;
 }
 
+interface UnparsedRoute {
+  file_name: string;
+  message: string;
+}
+
+interface RouteFiles {
+  routes: Route[];
+  unparsed: UnparsedRoute[];
+}
+
 interface DecodedRoute {
   positions: Position[];
 }
@@ -34,12 +45,14 @@ interface Position {
   loc: [number, number, number];
 }
 
-type MirroringAxis = "xy" | "x" | "y";
+type MirroringAxis = "auto" | "xy" | "x" | "y";
+
+// Most positions to request per route when charting
+const GRAPH_MAX_POINTS = 2000;
 
 const RouteManagerPage = () => {
   const mainContainerRef = useRef<HTMLDivElement>(null);
   const filtersRef = useRef<HTMLDivElement>(null);
-  const [isPythonInstalled, setIsPythonInstalled] = useState(false);
   const controlsRef = useRef<HTMLDivElement>(null);
   const [scrollAreaHeight, setScrollAreaHeight] = useState<number>(0);
   const [isDeleteModalOpen, setIsDeleteModalOpen] = useState(false);
@@ -72,23 +85,23 @@ const RouteManagerPage = () => {
   }, []);
 
   useEffect(() => {
-    // Check if Python is installed
-    const checkPythonInstallation = async () => {
-      try {
-        const isInstalled = await invoke<boolean>("check_python_installed");
-        setIsPythonInstalled(isInstalled);
-      } catch (error) {
-        console.error("Error checking Python installation:", error);
-      }
-    };
+    // Refresh the list when routes are recorded, deleted or rewritten
+    const unlisteners = ["route-added", "route-removed", "route-modified"].map(
+      (event) => listen<Route>(event, () => fetchRoutes())
+    );
 
-    checkPythonInstallation();
+    return () => {
+      unlisteners.forEach((unlisten) => unlisten.then((fn) => fn()));
+    };
   }, []);
 
   const fetchRoutes = async () => {
     try {
-      const data: Route[] = await invoke("get_route_files");
-      setRoutes(data);
+      const data: RouteFiles = await invoke("get_route_files");
+      setRoutes(data.routes);
+      data.unparsed.forEach((route) =>
+        console.warn(`Skipped route ${route.file_name}: ${route.message}`)
+      );
     } catch (error) {
       console.error("Error fetching routes:", error);
     }
@@ -130,9 +143,10 @@ const RouteManagerPage = () => {
 
   const confirmDelete = async () => {
     try {
-      for (const file of selectedRows) {
-        await invoke("delete_route_file", { file: file });
-      }
+      const result = await invoke("batch_delete_routes", {
+        selection: { files: Array.from(selectedRows) },
+      });
+      console.log("Deleted routes:", result);
       fetchRoutes();
       setSelectedRows(new Set());
       setIsDeleteModalOpen(false);
@@ -175,7 +189,10 @@ const RouteManagerPage = () => {
       const newDecodedRoutes = [];
 
       for (const fileName of selectedRows) {
-        const decodedData = await invoke("decode_route", { file: fileName });
+        const decodedData = await invoke("decode_route", {
+          file: fileName,
+          maxPoints: GRAPH_MAX_POINTS,
+        });
         newDecodedRoutes.push(decodedData);
       }
 
@@ -195,13 +212,12 @@ const RouteManagerPage = () => {
     setIsMirrorModalOpen(false); // Close the modal
 
     try {
-      for (const fileName of selectedRows) {
-        const response = await invoke("python_route_decoder", {
-          file: fileName,
-          axis: axis,
-        });
-        console.log("Mirrored route:", response);
-      }
+      const result = await invoke("batch_mirror_routes", {
+        selection: { files: Array.from(selectedRows) },
+        // Let the map registry pick the axis for each route's map
+        axis: axis === "auto" ? null : axis,
+      });
+      console.log("Mirrored routes:", result);
 
       fetchRoutes();
     } catch (error) {
@@ -227,6 +243,7 @@ const RouteManagerPage = () => {
   );
 
   const mirroringDescriptions: Record<MirroringAxis, string> = {
+    auto: "Uses the axis and centre from the map registry, or XY if the map is not known.",
     xy: "Mirrors on both axis. Use on symetrical maps such as Arx Novena",
     x: "Mirrors on only the X axis. Use on maps that are symetrical along the X axis.",
     y: "Mirrors on only the Y axis. Use on maps that are symetrical along the Y axis.",
@@ -249,7 +266,7 @@ const RouteManagerPage = () => {
         withCloseButton={false}
       >
         <div>
-          {(["xy", "x", "y"] as MirroringAxis[]).map((axis) => (
+          {(["auto", "xy", "x", "y"] as MirroringAxis[]).map((axis) => (
             <Tooltip key={axis} label={mirroringDescriptions[axis]} withArrow>
               <Button
                 variant="light"
@@ -294,36 +311,17 @@ const RouteManagerPage = () => {
                 </Button>
               </div>
               <div style={{ flexGrow: 1 }}>
-                {isPythonInstalled ? (
-                  <Button
-                    variant="light"
-                    color="cyan"
-                    style={{
-                      width: "100%",
-                      boxShadow: "0 4px 8px rgba(0, 0, 0, 0.15)",
-                    }}
-                    onClick={openMirrorModal}
-                  >
-                    Mirror Selected
-                  </Button>
-                ) : (
-                  <Tooltip
-                    label="Currently a Python install is required to mirror routes"
-                    withArrow
-                  >
-                    <Button
-                      variant="light"
-                      color="cyan"
-                      style={{
-                        width: "100%",
-                        boxShadow: "0 4px 8px rgba(0, 0, 0, 0.15)",
-                      }}
-                      disabled
-                    >
-                      Mirror Selected
-                    </Button>
-                  </Tooltip>
-                )}
+                <Button
+                  variant="light"
+                  color="cyan"
+                  style={{
+                    width: "100%",
+                    boxShadow: "0 4px 8px rgba(0, 0, 0, 0.15)",
+                  }}
+                  onClick={openMirrorModal}
+                >
+                  Mirror Selected
+                </Button>
               </div>
               <div style={{ flexGrow: 1 }}>
                 <Button
@@ -488,7 +486,8 @@ const RouteManagerPage = () => {
           withCloseButton={false}
         >
           <Text>
-            Are you sure you want to delete the following route files?
+            Are you sure you want to delete the following route files? They
+            are moved to the trash and can be restored until it is emptied.
           </Text>
           <ScrollArea style={{ maxHeight: "200px" }}>
             <ul>
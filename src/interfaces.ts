@@ -47,16 +47,22 @@ export interface ConfigFilesResult {
   tribes_input_ini: ConfigFile;
 }
 
-export interface IniField {
+export interface SettingSchema {
+  file: string;
+  sections: string[];
   key: string;
-  displayName: string;
-  type: "boolean" | "number";
+  display_name: string;
+  type: "boolean" | "integer" | "float";
+  min: number | null;
+  max: number | null;
+  default: string;
   description: string;
 }
 
 export interface Field {
   type: string;
   key: string;
+  sections: string[];
   displayName: string;
   description: string;
 }
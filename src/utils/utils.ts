@@ -1,5 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
-import { ConfigFilesResult, Config } from "../interfaces";
+import { ConfigFilesResult, Config, Field, SettingSchema } from "../interfaces";
 
 // Get the list of available packages from the backend
 export const getPackages = async (setPackages: (packages: any) => void) => {
@@ -38,45 +38,103 @@ export const checkAndFindGamePath = (
 
 export const fetchConfigFiles = async (setIniValues: (values: any) => void) => {
   try {
-    const result: ConfigFilesResult = await invoke("fetch_config_files");
+    const [result, schema] = await Promise.all([
+      invoke<ConfigFilesResult>("fetch_config_files"),
+      invoke<SettingSchema[]>("get_settings_schema"),
+    ]);
 
     // Parse ini files
-    const iniContent = parseIni(result.tribes_ini.content);
-    const inputIniContent = parseIni(result.tribes_input_ini.content);
-
-    // Initialize iniValues with fetched data if available, If value is true they are set to to, otherwise they are set to false
-    setIniValues({
-      DynamicLights: iniContent.DynamicLights === "True",
-      DepthOfField: iniContent.DepthOfField === "True",
-      MaxSmoothedFrameRate: parseInt(iniContent.MaxSmoothedFrameRate) || 240,
-      Bloom: iniContent.Bloom === "True",
-      MotionBlur: iniContent.MotionBlur === "True",
-      bSmoothFrameRate: iniContent.bSmoothFrameRate === "True",
-      bForceStaticTerrain: iniContent.bForceStaticTerrain === "True",
-      SpeedTreeLeaves: iniContent.SpeedTreeLeaves === "True",
-      SpeedTreeFronds: iniContent.SpeedTreeFronds === "True",
-      AllowRadialBlur: iniContent.AllowRadialBlur === "True",
-      OneFrameThreadLag: iniContent.OneFrameThreadLag === "True",
-      m_bTinyWeaponsEnabled: iniContent.m_bTinyWeaponsEnabled === "True",
-      UseVsync: iniContent.UseVsync === "True",
-      ResX: parseInt(iniContent.ResX) || 1920,
-      ResY: parseInt(iniContent.ResY) || 1080,
-      bEnableMouseSmoothing: inputIniContent.bEnableMouseSmoothing === "True",
-      FOVSetting: parseInt(inputIniContent.FOVSetting) || 90,
-      MouseSensitivity: parseFloat(inputIniContent.MouseSensitivity) || 10.0,
+    const iniContents: { [file: string]: IniSections } = {
+      "tribes.ini": parseIni(result.tribes_ini.content),
+      "TribesInput.ini": parseIni(result.tribes_input_ini.content),
+    };
+
+    // Read each setting from the first of its sections that has it, falling back
+    // to the schema's default when it is missing or invalid
+    const values: { [key: string]: boolean | number } = {};
+    schema.forEach((setting) => {
+      const sections = iniContents[setting.file] ?? {};
+      const key = setting.key.toLowerCase();
+      const value = setting.sections
+        .map((section) => sections[section.toLowerCase()]?.[key])
+        .find((found) => found !== undefined);
+      values[setting.key] =
+        parseSetting(setting, value) ?? parseSetting(setting, setting.default)!;
     });
+    setIniValues(values);
   } catch (error) {
     console.error("Error fetching config files:", error);
   }
 };
 
-function parseIni(iniContent: string): { [key: string]: string } {
-  const lines = iniContent.split("\n");
-  const iniObject: { [key: string]: string } = {};
-  lines.forEach((line) => {
-    const [key, value] = line.split("=");
-    if (key && value) {
-      iniObject[key.trim()] = value.trim();
+// Convert an ini value to the type of its setting, or undefined if it is not one
+function parseSetting(
+  setting: SettingSchema,
+  value: string | undefined
+): boolean | number | undefined {
+  if (value === undefined) {
+    return undefined;
+  }
+  switch (setting.type) {
+    case "boolean": {
+      const lower = value.trim().toLowerCase();
+      return lower === "true" ? true : lower === "false" ? false : undefined;
+    }
+    case "integer": {
+      const number = parseInt(value);
+      return Number.isNaN(number) ? undefined : number;
+    }
+    case "float": {
+      const number = parseFloat(value);
+      return Number.isFinite(number) ? number : undefined;
+    }
+  }
+}
+
+// Get the editable settings of a config file from the backend schema
+export const getSettingsFields = async (
+  file: string,
+  setFields: (fields: Field[]) => void
+) => {
+  try {
+    const schema: SettingSchema[] = await invoke("get_settings_schema");
+    setFields(
+      schema
+        .filter((setting) => setting.file === file)
+        .map((setting) => ({
+          key: setting.key,
+          sections: setting.sections,
+          displayName: setting.display_name,
+          type: setting.type,
+          description: setting.description,
+        }))
+    );
+  } catch (error) {
+    console.error("Failed to fetch settings schema:", error);
+  }
+};
+
+// Sections and keys of an ini file, lowercased as the game ignores their case
+type IniSections = { [section: string]: { [key: string]: string } };
+
+function parseIni(iniContent: string): IniSections {
+  const iniObject: IniSections = {};
+  let section: { [key: string]: string } = {};
+  iniContent.split(/\r?\n/).forEach((line) => {
+    const header = line.trim().match(/^\[(.*)\]$/);
+    if (header) {
+      const name = header[1].trim().toLowerCase();
+      iniObject[name] = iniObject[name] ?? {};
+      section = iniObject[name];
+      return;
+    }
+    const equals = line.indexOf("=");
+    if (equals > 0) {
+      const key = line.slice(0, equals).trim().toLowerCase();
+      // The first occurrence of a key is the one that counts
+      if (!(key in section)) {
+        section[key] = line.slice(equals + 1).trim();
+      }
     }
   });
   return iniObject;
@@ -111,21 +169,34 @@ export const handleInputChange = (
   fileKey: string,
   key: string,
   value: boolean | number,
+  sections: string[],
   setIniValues: React.Dispatch<
     React.SetStateAction<{ [key: string]: boolean | number }>
-  >
+  >,
+  onError?: (message: string) => void
 ) => {
+  let previous: boolean | number | undefined;
   setIniValues((prevValues) => {
-    const updatedValues = { ...prevValues, [key]: value };
-
-    // Prepare data for backend
-    const changes = [[key, value.toString()]];
-    const file = fileKey === "input" ? "TribesInput.ini" : "tribes.ini";
+    previous = prevValues[key];
+    return { ...prevValues, [key]: value };
+  });
 
-    // Call Rust function via Tauri command
-    invoke("update_ini_file", { file, changes }).catch(console.error);
+  // Prepare data for backend, only for the sections the game reads the key from
+  const changes =
+    sections.length > 0
+      ? sections.map((section) => ({ key, value: value.toString(), section }))
+      : [{ key, value: value.toString() }];
+  const file = fileKey === "input" ? "TribesInput.ini" : "tribes.ini";
 
-    return updatedValues;
+  // Call Rust function via Tauri command, putting the old value back if it is rejected
+  invoke("update_ini_file", { file, changes }).catch((error) => {
+    console.error(`Failed to update ${key}:`, error);
+    setIniValues((prevValues) =>
+      prevValues[key] === value && previous !== undefined
+        ? { ...prevValues, [key]: previous }
+        : prevValues
+    );
+    onError?.(String(error));
   });
 };
 